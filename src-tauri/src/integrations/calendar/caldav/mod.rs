@@ -1,8 +1,11 @@
 mod report;
 mod write;
 
-pub(super) use report::fetch_calendar_events;
+pub(super) use report::{
+    fetch_calendar_event, fetch_calendar_events, fetch_calendar_events_in_range,
+    fetch_calendar_events_matching,
+};
 pub(crate) use write::{
-    create_assignment_core, delete_assignment_core, update_assignment_core, AssignmentWrite,
-    CaldavSession,
+    calendar_url_for_href, create_assignment_core, delete_assignment_core, update_assignment_core,
+    AssignmentWrite, CaldavSession,
 };
@@ -3,7 +3,7 @@ use tauri_plugin_http::reqwest::Method;
 
 use super::super::ical::parse_ical_events;
 use super::super::types::RawVEvent;
-use super::write::CaldavSession;
+use super::write::{resolve_href, CaldavSession};
 
 pub(crate) async fn fetch_calendar_events(
     session: &CaldavSession,
@@ -11,11 +11,120 @@ pub(crate) async fn fetch_calendar_events(
     week_start: NaiveDate,
 ) -> Result<Vec<RawVEvent>, String> {
     let week_end = week_start + chrono::Duration::days(7);
-    let start_str = week_start.format("%Y%m%dT000000Z").to_string();
-    let end_str = week_end.format("%Y%m%dT000000Z").to_string();
+    fetch_calendar_events_in_range(session, calendar_url, week_start, week_end).await
+}
 
-    let body = build_report_body(&start_str, &end_str);
+/// `range_end` is exclusive, matching the CalDAV time-range semantics.
+pub(crate) async fn fetch_calendar_events_in_range(
+    session: &CaldavSession,
+    calendar_url: &str,
+    range_start: NaiveDate,
+    range_end: NaiveDate,
+) -> Result<Vec<RawVEvent>, String> {
+    let (start_str, end_str) = format_time_range(range_start, range_end);
+    let body = build_report_body(&start_str, &end_str, None);
+    send_report(session, calendar_url, body)
+        .await
+        .map_err(ReportFailure::into_message)
+}
 
+/// Like [`fetch_calendar_events_in_range`], but asks the server to only return events whose
+/// DESCRIPTION contains `marker`. Servers that reject the text-match filter (see
+/// [`rejects_text_match`]) are queried again without it, so callers must still filter the
+/// result themselves. Any other failure is returned as is.
+pub(crate) async fn fetch_calendar_events_matching(
+    session: &CaldavSession,
+    calendar_url: &str,
+    range_start: NaiveDate,
+    range_end: NaiveDate,
+    marker: &str,
+) -> Result<Vec<RawVEvent>, String> {
+    let (start_str, end_str) = format_time_range(range_start, range_end);
+    let body = build_report_body(&start_str, &end_str, Some(marker));
+    match send_report(session, calendar_url, body).await {
+        Ok(events) => Ok(events),
+        Err(ReportFailure::Status(status)) if rejects_text_match(status) => {
+            eprintln!(
+                "caldav: text-match REPORT rejected with HTTP {status}, retrying without filter"
+            );
+            fetch_calendar_events_in_range(session, calendar_url, range_start, range_end).await
+        }
+        Err(failure) => Err(failure.into_message()),
+    }
+}
+
+/// Statuses with which servers turn down an unsupported filter. Auth errors, missing
+/// calendars and server failures would fail the unfiltered REPORT just the same.
+fn rejects_text_match(status: u16) -> bool {
+    matches!(status, 400 | 403 | 415 | 501)
+}
+
+/// Reads a single event resource. A resource that no longer exists yields `None`.
+pub(crate) async fn fetch_calendar_event(
+    session: &CaldavSession,
+    href: &str,
+) -> Result<Option<RawVEvent>, String> {
+    let resource_url = resolve_href(href, &session.base_url)?;
+    let response = session
+        .client
+        .get(&resource_url)
+        .basic_auth(&session.username, Some(&session.password))
+        .send()
+        .await
+        .map_err(|e| format!("Kalendereintrag konnte nicht abgerufen werden: {e}"))?;
+
+    let status = response.status().as_u16();
+    if status == 404 {
+        return Ok(None);
+    }
+    if !(200..300).contains(&status) {
+        return Err(ReportFailure::Status(status).into_message());
+    }
+
+    let ical_text = response
+        .text()
+        .await
+        .map_err(|e| format!("Kalendereintrag konnte nicht gelesen werden: {e}"))?;
+    let mut events = parse_ical_events(&ical_text)?;
+    if events.is_empty() {
+        return Ok(None);
+    }
+    let mut event = events.swap_remove(0);
+    event.href = href.to_string();
+    Ok(Some(event))
+}
+
+enum ReportFailure {
+    Status(u16),
+    Other(String),
+}
+
+impl ReportFailure {
+    fn into_message(self) -> String {
+        match self {
+            ReportFailure::Status(401) => {
+                "Authentifizierung fehlgeschlagen. ZEP-Zugangsdaten prüfen.".to_string()
+            }
+            ReportFailure::Status(status) => {
+                format!("CalDAV-Server antwortete mit HTTP {status}")
+            }
+            ReportFailure::Other(message) => message,
+        }
+    }
+}
+
+fn format_time_range(range_start: NaiveDate, range_end: NaiveDate) -> (String, String) {
+    (
+        range_start.format("%Y%m%dT000000Z").to_string(),
+        range_end.format("%Y%m%dT000000Z").to_string(),
+    )
+}
+
+async fn send_report(
+    session: &CaldavSession,
+    calendar_url: &str,
+    body: String,
+) -> Result<Vec<RawVEvent>, ReportFailure> {
     let response = session
         .client
         .request(
@@ -28,30 +137,42 @@ pub(crate) async fn fetch_calendar_events(
         .body(body)
         .send()
         .await
-        .map_err(|e| format!("Kalender konnte nicht abgerufen werden: {e}"))?;
+        .map_err(|e| {
+            ReportFailure::Other(format!("Kalender konnte nicht abgerufen werden: {e}"))
+        })?;
 
     let status = response.status().as_u16();
-    if status == 401 {
-        return Err("Authentifizierung fehlgeschlagen. ZEP-Zugangsdaten prüfen.".to_string());
-    }
     if !(200..300).contains(&status) {
-        return Err(format!("CalDAV-Server antwortete mit HTTP {status}"));
+        return Err(ReportFailure::Status(status));
     }
 
-    let xml_text = response
-        .text()
-        .await
-        .map_err(|e| format!("Kalenderantwort konnte nicht gelesen werden: {e}"))?;
+    let xml_text = response.text().await.map_err(|e| {
+        ReportFailure::Other(format!("Kalenderantwort konnte nicht gelesen werden: {e}"))
+    })?;
 
-    parse_caldav_report(&xml_text)
-        .map_err(|e| format!("Kalenderantwort konnte nicht verarbeitet werden: {e}"))
+    parse_caldav_report(&xml_text).map_err(|e| {
+        ReportFailure::Other(format!(
+            "Kalenderantwort konnte nicht verarbeitet werden: {e}"
+        ))
+    })
 }
 
-fn build_report_body(start: &str, end: &str) -> String {
+fn build_report_body(start: &str, end: &str, description_match: Option<&str>) -> String {
     debug_assert!(
         start.len() == 16 && end.len() == 16,
         "CalDAV timestamp must be 16 chars: got start={start:?} end={end:?}"
     );
+    let text_match = description_match
+        .map(|marker| {
+            format!(
+                r#"
+        <c:prop-filter name="DESCRIPTION">
+          <c:text-match collation="i;octet">{}</c:text-match>
+        </c:prop-filter>"#,
+                escape_xml_text(marker)
+            )
+        })
+        .unwrap_or_default();
     format!(
         r#"<?xml version="1.0" encoding="utf-8"?>
 <c:calendar-query xmlns:d="DAV:" xmlns:c="urn:ietf:params:xml:ns:caldav">
@@ -62,7 +183,7 @@ fn build_report_body(start: &str, end: &str) -> String {
   <c:filter>
     <c:comp-filter name="VCALENDAR">
       <c:comp-filter name="VEVENT">
-        <c:time-range start="{start}" end="{end}"/>
+        <c:time-range start="{start}" end="{end}"/>{text_match}
       </c:comp-filter>
     </c:comp-filter>
   </c:filter>
@@ -70,6 +191,13 @@ fn build_report_body(start: &str, end: &str) -> String {
     )
 }
 
+fn escape_xml_text(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
 fn parse_caldav_report(xml_text: &str) -> Result<Vec<RawVEvent>, String> {
     let doc = roxmltree::Document::parse(xml_text)
         .map_err(|e| format!("XML konnte nicht geparst werden: {e}"))?;
@@ -108,6 +236,47 @@ fn parse_caldav_report(xml_text: &str) -> Result<Vec<RawVEvent>, String> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn build_report_body_omits_text_match_without_marker() {
+        let body = build_report_body("20260504T000000Z", "20260511T000000Z", None);
+
+        assert!(body.contains(r#"<c:time-range start="20260504T000000Z" end="20260511T000000Z"/>"#));
+        assert!(!body.contains("prop-filter"));
+    }
+
+    #[test]
+    fn build_report_body_filters_description_by_marker() {
+        let body = build_report_body(
+            "20260504T000000Z",
+            "20260511T000000Z",
+            Some("daylite:/v1/projects/3001"),
+        );
+
+        assert!(body.contains(r#"<c:prop-filter name="DESCRIPTION">"#));
+        assert!(body.contains(
+            r#"<c:text-match collation="i;octet">daylite:/v1/projects/3001</c:text-match>"#
+        ));
+        assert!(roxmltree::Document::parse(&body).is_ok());
+    }
+
+    #[test]
+    fn build_report_body_escapes_marker_for_xml() {
+        let body = build_report_body("20260504T000000Z", "20260511T000000Z", Some("a<b&c"));
+
+        assert!(body.contains("a&lt;b&amp;c"));
+        assert!(roxmltree::Document::parse(&body).is_ok());
+    }
+
+    #[test]
+    fn only_filter_rejections_fall_back_to_an_unfiltered_report() {
+        for status in [400, 403, 415, 501] {
+            assert!(rejects_text_match(status), "HTTP {status}");
+        }
+        for status in [401, 404, 500, 502, 503] {
+            assert!(!rejects_text_match(status), "HTTP {status}");
+        }
+    }
+
     #[test]
     fn parse_caldav_report_returns_href_with_each_event() {
         let xml = r#"<?xml version="1.0" encoding="utf-8"?>
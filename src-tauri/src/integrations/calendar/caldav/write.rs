@@ -15,6 +15,25 @@ pub(crate) struct AssignmentWrite {
     pub(crate) date: String,
     pub(crate) project_ref: String,
     pub(crate) project_name: String,
+    // HH:MM, local floating time.
+    pub(crate) start_time: String,
+    pub(crate) end_time: String,
+    // Position within the day; None leaves it to the next re-slotting.
+    pub(crate) order_index: Option<u32>,
+}
+
+impl AssignmentWrite {
+    fn payload(&self, uid: &str) -> String {
+        build_ical_payload(
+            uid,
+            &self.date,
+            &self.project_name,
+            &self.project_ref,
+            &self.start_time,
+            &self.end_time,
+            self.order_index,
+        )
+    }
 }
 
 pub(crate) async fn create_assignment_core(
@@ -32,7 +51,7 @@ pub(crate) async fn create_assignment_core(
     }
 
     let uid = Uuid::new_v4().to_string();
-    let payload = build_ical_payload(&uid, &write.date, &write.project_name, &write.project_ref);
+    let payload = write.payload(&uid);
 
     let base = calendar_url.trim_end_matches('/');
     let resource_url = format!("{base}/{uid}.ics");
@@ -74,7 +93,7 @@ pub(crate) async fn update_assignment_core(
         );
     }
 
-    let payload = build_ical_payload(uid, &write.date, &write.project_name, &write.project_ref);
+    let payload = write.payload(uid);
 
     eprintln!("calendar: update_assignment PUT {resource_url}");
 
@@ -136,7 +155,7 @@ pub(crate) async fn delete_assignment_core(
 /// CalDAV servers return root-absolute hrefs; joining one onto a `base_url` that
 /// already contains a path would duplicate the path segment and produce a 404,
 /// so the href is resolved against the scheme+host origin only.
-fn resolve_href(href: &str, base_url: &str) -> Result<String, String> {
+pub(super) fn resolve_href(href: &str, base_url: &str) -> Result<String, String> {
     if href.starts_with("http://") || href.starts_with("https://") {
         return Ok(href.to_string());
     }
@@ -148,6 +167,16 @@ fn resolve_href(href: &str, base_url: &str) -> Result<String, String> {
     Ok(resolved.to_string())
 }
 
+/// The calendar collection an event resource belongs to, i.e. its href without the
+/// trailing `<uid>.ics` segment.
+pub(crate) fn calendar_url_for_href(href: &str, base_url: &str) -> Result<String, String> {
+    let resource_url = resolve_href(href, base_url)?;
+    resource_url
+        .rsplit_once('/')
+        .map(|(collection, _)| format!("{collection}/"))
+        .ok_or_else(|| format!("Kalender-URL konnte nicht aufgelöst werden: {href}"))
+}
+
 /// Safety guard: assignment writes must never land in an absence calendar, even
 /// if the store is misconfigured (primary == absence) or an href is corrupted.
 fn targets_absence_calendar(target_url: &str, absence_urls: &[String]) -> bool {
@@ -182,6 +211,16 @@ mod tests {
         );
     }
 
+    #[test]
+    fn calendar_url_for_href_strips_resource_segment() {
+        let result = calendar_url_for_href(
+            "/caldav/admin/emp-1/uid-1.ics",
+            "https://app.zep.de/caldav/admin",
+        )
+        .unwrap();
+        assert_eq!(result, "https://app.zep.de/caldav/admin/emp-1/");
+    }
+
     #[tokio::test]
     #[ignore = "VCR: requires live CalDAV server credentials"]
     async fn create_assignment_core_sends_put_and_returns_href() {
@@ -208,6 +247,9 @@ mod tests {
                 date: "2026-05-06".to_string(),
                 project_ref: "/v1/projects/42".to_string(),
                 project_name: "Testprojekt".to_string(),
+                start_time: "08:00".to_string(),
+                end_time: "16:00".to_string(),
+                order_index: None,
             },
         )
         .await
@@ -245,6 +287,9 @@ mod tests {
                 date: "2026-05-07".to_string(),
                 project_ref: "/v1/projects/42".to_string(),
                 project_name: "Aktualisiertes Projekt".to_string(),
+                start_time: "08:00".to_string(),
+                end_time: "16:00".to_string(),
+                order_index: None,
             },
         )
         .await
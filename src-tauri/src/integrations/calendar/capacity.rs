@@ -0,0 +1,251 @@
+use chrono::NaiveDate;
+
+use super::types::{CalendarCellEvent, CalendarEventKind, DayCapacity};
+use crate::integrations::local_store::WorkingTimeModel;
+
+/// Daily working window of `model` in minutes since midnight. A model with unparsable or
+/// inverted times falls back to the company default window instead of yielding no capacity.
+pub(crate) fn working_window(model: &WorkingTimeModel) -> (u32, u32) {
+    match (
+        parse_minutes(&model.start_time),
+        parse_minutes(&model.end_time),
+    ) {
+        (Some(start), Some(end)) if start < end => (start, end),
+        _ => {
+            let fallback = WorkingTimeModel::default();
+            (
+                parse_minutes(&fallback.start_time).unwrap_or_default(),
+                parse_minutes(&fallback.end_time).unwrap_or_default(),
+            )
+        }
+    }
+}
+
+/// The model's daily window as HH:MM strings.
+pub(crate) fn working_window_times(model: &WorkingTimeModel) -> (String, String) {
+    let (start, end) = working_window(model);
+    (format_minutes(start), format_minutes(end))
+}
+
+/// Remaining capacity of one employee on `date`, given that day's absence events.
+/// Days outside the working-time model have no capacity. Absences without a time window
+/// block the whole day; timed absences only remove the part of the working window they cover.
+pub(crate) fn day_capacity(
+    date: NaiveDate,
+    absences: &[CalendarCellEvent],
+    model: &WorkingTimeModel,
+) -> DayCapacity {
+    if !model.works_on(date) {
+        return day_off(date);
+    }
+    let date_str = date.format("%Y-%m-%d").to_string();
+    let (work_start, work_end) = working_window(model);
+
+    let mut blocked: Vec<(u32, u32)> = Vec::new();
+    for absence in absences
+        .iter()
+        .filter(|e| e.kind == CalendarEventKind::Absence && e.date == date_str)
+    {
+        match absence_window(absence) {
+            Some((start, end)) => blocked.push((start.max(work_start), end.min(work_end))),
+            None => blocked.push((work_start, work_end)),
+        }
+    }
+    blocked.retain(|(start, end)| start < end);
+    blocked.sort();
+
+    let mut free_windows = Vec::new();
+    let mut cursor = work_start;
+    for (start, end) in blocked {
+        if start > cursor {
+            free_windows.push((cursor, start));
+        }
+        cursor = cursor.max(end);
+    }
+    if cursor < work_end {
+        free_windows.push((cursor, work_end));
+    }
+
+    let available_minutes = free_windows.iter().map(|(s, e)| e - s).sum();
+    // Earliest of the longest windows, so placement is deterministic.
+    let largest =
+        free_windows
+            .iter()
+            .copied()
+            .reduce(|best, w| if w.1 - w.0 > best.1 - best.0 { w } else { best });
+
+    DayCapacity {
+        date: date_str,
+        is_working_day: true,
+        available_minutes,
+        free_start_time: largest.map(|(s, _)| format_minutes(s)),
+        free_end_time: largest.map(|(_, e)| format_minutes(e)),
+    }
+}
+
+/// Capacity of a day nobody is expected to work, e.g. a public holiday.
+pub(crate) fn day_off(date: NaiveDate) -> DayCapacity {
+    DayCapacity {
+        date: date.format("%Y-%m-%d").to_string(),
+        is_working_day: false,
+        available_minutes: 0,
+        free_start_time: None,
+        free_end_time: None,
+    }
+}
+
+/// Minutes covered by a timed absence, or None for an all-day absence.
+pub(crate) fn partial_absence_minutes(
+    start_time: Option<&str>,
+    end_time: Option<&str>,
+) -> Option<u32> {
+    let start = parse_minutes(start_time?)?;
+    let end = parse_minutes(end_time?)?;
+    end.checked_sub(start).filter(|minutes| *minutes > 0)
+}
+
+fn absence_window(absence: &CalendarCellEvent) -> Option<(u32, u32)> {
+    absence.absence_minutes?;
+    let start = parse_minutes(absence.start_time.as_deref()?)?;
+    let end = parse_minutes(absence.end_time.as_deref()?)?;
+    Some((start, end))
+}
+
+pub(crate) fn parse_minutes(hh_mm: &str) -> Option<u32> {
+    let (hours, minutes) = hh_mm.split_once(':')?;
+    let hours: u32 = hours.parse().ok()?;
+    let minutes: u32 = minutes.parse().ok()?;
+    (hours <= 24 && minutes < 60).then_some(hours * 60 + minutes)
+}
+
+pub(crate) fn format_minutes(minutes: u32) -> String {
+    format!("{:02}:{:02}", minutes / 60, minutes % 60)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::local_store::WorkingDay;
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 4, 28).unwrap()
+    }
+
+    fn model() -> WorkingTimeModel {
+        WorkingTimeModel::default()
+    }
+
+    fn absence(start: Option<&str>, end: Option<&str>) -> CalendarCellEvent {
+        CalendarCellEvent {
+            uid: "abs-1".to_string(),
+            kind: CalendarEventKind::Absence,
+            title: "Arzt".to_string(),
+            project_status: None,
+            date: "2026-04-28".to_string(),
+            start_time: start.map(str::to_string),
+            end_time: end.map(str::to_string),
+            href: None,
+            project_ref: None,
+            absence_category: None,
+            absence_minutes: partial_absence_minutes(start, end),
+            order_index: None,
+        }
+    }
+
+    #[test]
+    fn full_workday_is_available_without_absences() {
+        let capacity = day_capacity(date(), &[], &model());
+
+        assert_eq!(capacity.available_minutes, 480);
+        assert_eq!(capacity.free_start_time, Some("08:00".to_string()));
+        assert_eq!(capacity.free_end_time, Some("16:00".to_string()));
+    }
+
+    #[test]
+    fn all_day_absence_blocks_the_day() {
+        let capacity = day_capacity(date(), &[absence(None, None)], &model());
+
+        assert_eq!(capacity.available_minutes, 0);
+        assert_eq!(capacity.free_start_time, None);
+    }
+
+    #[test]
+    fn morning_absence_leaves_the_afternoon() {
+        let capacity = day_capacity(date(), &[absence(Some("08:00"), Some("12:00"))], &model());
+
+        assert_eq!(capacity.available_minutes, 240);
+        assert_eq!(capacity.free_start_time, Some("12:00".to_string()));
+        assert_eq!(capacity.free_end_time, Some("16:00".to_string()));
+    }
+
+    #[test]
+    fn absence_in_the_middle_picks_the_longest_remaining_window() {
+        let capacity = day_capacity(date(), &[absence(Some("09:00"), Some("10:30"))], &model());
+
+        assert_eq!(capacity.available_minutes, 390);
+        assert_eq!(capacity.free_start_time, Some("10:30".to_string()));
+        assert_eq!(capacity.free_end_time, Some("16:00".to_string()));
+    }
+
+    #[test]
+    fn absences_outside_the_workday_or_on_other_days_are_ignored() {
+        let mut other_day = absence(None, None);
+        other_day.date = "2026-04-29".to_string();
+        let evening = absence(Some("17:00"), Some("19:00"));
+
+        let capacity = day_capacity(date(), &[other_day, evening], &model());
+
+        assert_eq!(capacity.available_minutes, 480);
+    }
+
+    #[test]
+    fn non_working_days_have_no_capacity() {
+        let part_time = WorkingTimeModel {
+            working_days: vec![WorkingDay::Monday],
+            ..WorkingTimeModel::default()
+        };
+
+        let capacity = day_capacity(date(), &[], &part_time);
+
+        assert!(!capacity.is_working_day);
+        assert_eq!(capacity.available_minutes, 0);
+        assert_eq!(capacity.free_start_time, None);
+    }
+
+    #[test]
+    fn capacity_follows_the_model_window() {
+        let short_day = WorkingTimeModel {
+            start_time: "07:00".to_string(),
+            end_time: "11:30".to_string(),
+            ..WorkingTimeModel::default()
+        };
+
+        let capacity = day_capacity(date(), &[absence(Some("10:00"), Some("12:00"))], &short_day);
+
+        assert_eq!(capacity.available_minutes, 180);
+        assert_eq!(capacity.free_start_time, Some("07:00".to_string()));
+        assert_eq!(capacity.free_end_time, Some("10:00".to_string()));
+    }
+
+    #[test]
+    fn invalid_model_times_fall_back_to_the_default_window() {
+        let broken = WorkingTimeModel {
+            start_time: "16:00".to_string(),
+            end_time: "08:00".to_string(),
+            ..WorkingTimeModel::default()
+        };
+
+        assert_eq!(working_window(&broken), (480, 960));
+    }
+
+    #[test]
+    fn partial_absence_minutes_requires_a_valid_window() {
+        assert_eq!(
+            partial_absence_minutes(Some("08:00"), Some("12:00")),
+            Some(240)
+        );
+        assert_eq!(partial_absence_minutes(Some("12:00"), Some("08:00")), None);
+        assert_eq!(partial_absence_minutes(None, Some("12:00")), None);
+        assert_eq!(partial_absence_minutes(Some("xx"), Some("12:00")), None);
+    }
+}
@@ -6,14 +6,24 @@ use std::time::Duration;
 use tauri_plugin_http::reqwest;
 
 use super::caldav::{
-    create_assignment_core, delete_assignment_core, fetch_calendar_events, update_assignment_core,
-    AssignmentWrite, CaldavSession,
+    calendar_url_for_href, create_assignment_core, delete_assignment_core, fetch_calendar_events,
+    fetch_calendar_events_in_range, update_assignment_core, AssignmentWrite, CaldavSession,
 };
+use super::capacity::{day_capacity, day_off, format_minutes, working_window_times};
 use super::events::{
-    classify_event, map_absence_raw_events_for_week, resolve_event, sort_events_absences_first,
+    classify_event, has_duplicate_assignment, has_overlapping_assignment,
+    map_absence_raw_events_for_week, resolve_event, sort_events_absences_first,
+};
+use super::slots::{
+    assignment_placement, free_window, is_day_off, redistribute_day, redistribute_day_after_write,
 };
 use super::types::{CalendarCellEvent, EmployeeWeekEvents, PendingEvent};
-use crate::integrations::local_store::{DayliteCache, LocalStore};
+use crate::integrations::daylite::assignment_mirror::{
+    spawn_assignment_mirror, MirroredAssignment,
+};
+use crate::integrations::daylite::project_status::spawn_assignment_status_rules;
+use crate::integrations::holidays::days_off;
+use crate::integrations::local_store::{EmployeeSetting, LocalStore, WorkingTimeModel};
 
 #[tauri::command]
 #[specta::specta]
@@ -49,9 +59,10 @@ pub async fn load_week_events(
     let api_results = fetch_uncached_projects(app, &store, &fetches).await;
 
     Ok(assemble_week_events(
+        week_start_date,
         fetches,
         error_results,
-        &store.daylite_cache,
+        &store,
         &api_results,
     ))
 }
@@ -76,6 +87,7 @@ fn employees_with_error(store: &LocalStore, message: &str) -> Vec<EmployeeWeekEv
             employee_reference: s.daylite_contact_reference.clone(),
             events: vec![],
             error: Some(message.to_string()),
+            day_capacities: vec![],
         })
         .collect()
 }
@@ -132,7 +144,13 @@ async fn fetch_week_for_employees(
                     employee_reference,
                     pending: raw_events.into_iter().map(classify_event).collect(),
                     absences: absence_result
-                        .map(|raw| map_absence_raw_events_for_week(raw, week_start))
+                        .map(|raw| {
+                            map_absence_raw_events_for_week(
+                                raw,
+                                week_start,
+                                &store.absence_settings.category_rules,
+                            )
+                        })
                         .unwrap_or_default(),
                 });
             }
@@ -141,6 +159,7 @@ async fn fetch_week_for_employees(
                     employee_reference,
                     events: vec![],
                     error: Some(error_msg),
+                    day_capacities: vec![],
                 });
             }
         }
@@ -184,9 +203,10 @@ async fn fetch_uncached_projects(
 }
 
 fn assemble_week_events(
+    week_start: NaiveDate,
     fetches: Vec<EmployeeFetch>,
     error_results: Vec<EmployeeWeekEvents>,
-    cache: &DayliteCache,
+    store: &LocalStore,
     api_results: &HashMap<String, Option<(String, String)>>,
 ) -> Vec<EmployeeWeekEvents> {
     let mut results = error_results;
@@ -194,17 +214,37 @@ fn assemble_week_events(
         let mut events: Vec<CalendarCellEvent> = fetch
             .pending
             .into_iter()
-            .map(|p| resolve_event(p, cache, api_results))
+            .map(|p| resolve_event(p, &store.daylite_cache, api_results))
             .collect();
         events.extend(fetch.absences);
         // Deduplicate by UID to guard against CalDAV servers redelivering the same event.
         let mut seen_uids = HashSet::new();
         events.retain(|e| seen_uids.insert(e.uid.clone()));
         sort_events_absences_first(&mut events);
+        let model = store.working_time_model_for(&fetch.employee_reference);
+        // Only cached holidays are used; the holiday commands keep the cache filled.
+        let days_off = days_off(
+            store,
+            &fetch.employee_reference,
+            week_start,
+            week_start + chrono::Duration::days(6),
+        );
+        let day_capacities = (0..7)
+            .map(|offset| {
+                let date = week_start + chrono::Duration::days(offset);
+                let date_str = date.format("%Y-%m-%d").to_string();
+                if days_off.contains(&date_str) {
+                    day_off(date)
+                } else {
+                    day_capacity(date, &events, model)
+                }
+            })
+            .collect();
         results.push(EmployeeWeekEvents {
             employee_reference: fetch.employee_reference,
             events,
             error: None,
+            day_capacities,
         });
     }
 
@@ -230,6 +270,15 @@ pub struct UpdateAssignmentInput {
     pub project_name: String,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct ReorderAssignmentsInput {
+    pub employee_reference: String,
+    pub date: String,
+    // UIDs in the new order. Assignments not listed keep their relative order behind them.
+    pub uids: Vec<String>,
+}
+
 #[tauri::command]
 #[specta::specta]
 pub async fn create_assignment(
@@ -239,30 +288,147 @@ pub async fn create_assignment(
     let store =
         crate::integrations::local_store::load_local_store(app).map_err(|e| e.user_message)?;
 
-    let calendar_url = store
+    let setting = store
         .employee_settings
         .iter()
-        .find(|s| s.daylite_contact_reference == input.employee_reference)
-        .and_then(|s| s.zep_primary_calendar.as_deref())
-        .filter(|u| !u.is_empty())
-        .ok_or_else(|| "Kein Kalender für diesen Mitarbeiter konfiguriert.".to_string())?
-        .to_string();
+        .find(|s| s.daylite_contact_reference == input.employee_reference);
+    let calendar_url = primary_calendar_url(setting)?;
+    let absence_url = setting
+        .and_then(|s| s.zep_absence_calendar.as_deref())
+        .filter(|u| !u.is_empty());
 
     let session = load_caldav_session(&store)?;
 
-    create_assignment_core(
+    let day = parse_assignment_date(&input.date)?;
+    let day_off = is_day_off(&store, &input.employee_reference, day);
+    let (start, end) = free_window(
+        &session,
+        absence_url,
+        day,
+        day_off,
+        &store.absence_settings.category_rules,
+        store.working_time_model_for(&input.employee_reference),
+    )
+    .await
+    .ok_or_else(|| {
+        if day_off {
+            DAY_OFF_ERROR.to_string()
+        } else {
+            "Der Mitarbeiter ist an diesem Tag ganztägig abwesend.".to_string()
+        }
+    })?;
+    let (start_time, end_time) = (format_minutes(start), format_minutes(end));
+
+    ensure_no_conflicting_assignment(
+        &session,
+        &calendar_url,
+        &input.date,
+        &input.project_ref,
+        (&start_time, &end_time),
+        None,
+    )
+    .await?;
+
+    let href = create_assignment_core(
         &session,
         &calendar_url,
         &AssignmentWrite {
             date: input.date,
-            project_ref: input.project_ref,
+            project_ref: input.project_ref.clone(),
             project_name: input.project_name,
+            start_time,
+            end_time,
+            // Unordered, so the next re-slotting appends it after the planned ones.
+            order_index: None,
         },
     )
+    .await?;
+
+    redistribute_day_after_write(&session, &store, &calendar_url, day).await;
+    spawn_assignment_status_rules(&store, input.project_ref.clone(), day);
+    if let Some(uid) = assignment_uid(&href) {
+        let assignment = MirroredAssignment {
+            date: day,
+            project_ref: input.project_ref,
+            employee_reference: input.employee_reference,
+        };
+        spawn_assignment_mirror(&store, uid.to_string(), Some(assignment));
+    }
+    Ok(href)
+}
+
+const DAY_OFF_ERROR: &str = "Der Mitarbeiter hat an diesem Tag frei (Feiertag oder Betriebsruhe).";
+
+fn primary_calendar_url(setting: Option<&EmployeeSetting>) -> Result<String, String> {
+    setting
+        .and_then(|s| s.zep_primary_calendar.as_deref())
+        .filter(|u| !u.is_empty())
+        .map(str::to_string)
+        .ok_or_else(|| "Kein Kalender für diesen Mitarbeiter konfiguriert.".to_string())
+}
+
+fn parse_assignment_date(date: &str) -> Result<NaiveDate, String> {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d").map_err(|_| format!("Ungültiges Datum: {date}"))
+}
+
+/// Rejects a write that would plan the same project twice for one employee on one day, or
+/// whose `window` (start and end time) overlaps an assignment the re-slotting cannot move.
+async fn ensure_no_conflicting_assignment(
+    session: &CaldavSession,
+    calendar_url: &str,
+    date: &str,
+    project_ref: &str,
+    window: (&str, &str),
+    exclude_uid: Option<&str>,
+) -> Result<(), String> {
+    let day = parse_assignment_date(date)?;
+    let existing = match fetch_calendar_events_in_range(
+        session,
+        calendar_url,
+        day,
+        day + chrono::Duration::days(1),
+    )
     .await
+    {
+        Ok(raw_events) => raw_events
+            .into_iter()
+            .map(classify_event)
+            .collect::<Vec<_>>(),
+        Err(e) => {
+            // The write itself reports connectivity problems; a failed pre-check must not block it.
+            eprintln!("calendar: conflict check skipped for '{calendar_url}': {e}");
+            return Ok(());
+        }
+    };
+
+    if has_duplicate_assignment(&existing, date, project_ref, exclude_uid) {
+        return Err(
+            "Dieses Projekt ist für diesen Mitarbeiter an diesem Tag bereits eingeplant."
+                .to_string(),
+        );
+    }
+    let (start_time, end_time) = window;
+    if has_overlapping_assignment(&existing, date, start_time, end_time, exclude_uid) {
+        return Err(
+            "Der Einsatz überschneidet sich mit einem anderen Einsatz dieses Mitarbeiters."
+                .to_string(),
+        );
+    }
+    Ok(())
+}
+
+/// Working-time model of the employee owning `calendar_url`, or the company default.
+pub(super) fn model_for_calendar<'a>(
+    store: &'a LocalStore,
+    calendar_url: &str,
+) -> &'a WorkingTimeModel {
+    store
+        .employee_for_primary_calendar(calendar_url)
+        .map(|s| store.working_time_model_for(&s.daylite_contact_reference))
+        .unwrap_or(&store.default_working_time_model)
 }
 
-fn load_caldav_session(
+pub(super) fn load_caldav_session(
     store: &crate::integrations::local_store::LocalStore,
 ) -> Result<CaldavSession, String> {
     let credentials = crate::integrations::zep::load_zep_credentials_from_keychain()
@@ -303,17 +469,63 @@ pub async fn update_assignment(
         crate::integrations::local_store::load_local_store(app).map_err(|e| e.user_message)?;
     let session = load_caldav_session(&store)?;
 
+    let calendar_url = calendar_url_for_href(&input.href, &session.base_url)?;
+    let (start_time, end_time) = working_window_times(model_for_calendar(&store, &calendar_url));
+    ensure_no_conflicting_assignment(
+        &session,
+        &calendar_url,
+        &input.date,
+        &input.project_ref,
+        (&start_time, &end_time),
+        Some(&input.uid),
+    )
+    .await?;
+
+    let target_day = parse_assignment_date(&input.date)?;
+    let source = assignment_placement(&session, &input.href).await;
+    let source_day = source.as_ref().map(|placement| placement.day);
+    // A moved assignment joins the end of its new day; otherwise it keeps its place.
+    let order_index = source
+        .filter(|placement| placement.day == target_day)
+        .and_then(|placement| placement.order_index);
+    // Assignments already on a day off may still be edited, but none are moved onto one.
+    let moved = source_day != Some(target_day);
+    if moved
+        && store
+            .employee_for_primary_calendar(&calendar_url)
+            .is_some_and(|s| is_day_off(&store, &s.daylite_contact_reference, target_day))
+    {
+        return Err(DAY_OFF_ERROR.to_string());
+    }
+
     update_assignment_core(
         &session,
         &input.href,
         &input.uid,
         &AssignmentWrite {
             date: input.date,
-            project_ref: input.project_ref,
+            project_ref: input.project_ref.clone(),
             project_name: input.project_name,
+            start_time,
+            end_time,
+            order_index,
         },
     )
-    .await
+    .await?;
+
+    redistribute_day_after_write(&session, &store, &calendar_url, target_day).await;
+    if let Some(source_day) = source_day.filter(|d| *d != target_day) {
+        redistribute_day_after_write(&session, &store, &calendar_url, source_day).await;
+    }
+    if let Some(employee) = store.employee_for_primary_calendar(&calendar_url) {
+        let assignment = MirroredAssignment {
+            date: target_day,
+            project_ref: input.project_ref,
+            employee_reference: employee.daylite_contact_reference.clone(),
+        };
+        spawn_assignment_mirror(&store, input.uid, Some(assignment));
+    }
+    Ok(())
 }
 
 #[tauri::command]
@@ -323,5 +535,47 @@ pub async fn delete_assignment(app: tauri::AppHandle, href: String) -> Result<()
         crate::integrations::local_store::load_local_store(app).map_err(|e| e.user_message)?;
     let session = load_caldav_session(&store)?;
 
-    delete_assignment_core(&session, &href).await
+    let calendar_url = calendar_url_for_href(&href, &session.base_url)?;
+    let day = assignment_placement(&session, &href)
+        .await
+        .map(|placement| placement.day);
+
+    delete_assignment_core(&session, &href).await?;
+
+    if let Some(day) = day {
+        redistribute_day_after_write(&session, &store, &calendar_url, day).await;
+    }
+    if let Some(uid) = assignment_uid(&href) {
+        spawn_assignment_mirror(&store, uid.to_string(), None);
+    }
+    Ok(())
+}
+
+/// Assignment resources are stored as `<uid>.ics`, so the UID is the href's file name.
+pub(super) fn assignment_uid(href: &str) -> Option<&str> {
+    href.rsplit('/')
+        .next()
+        .and_then(|name| name.strip_suffix(".ics"))
+        .filter(|uid| !uid.is_empty())
+}
+
+/// Stores a new in-day order for an employee's assignments and re-slots the day to match.
+#[tauri::command]
+#[specta::specta]
+pub async fn reorder_assignments(
+    app: tauri::AppHandle,
+    input: ReorderAssignmentsInput,
+) -> Result<(), String> {
+    let store =
+        crate::integrations::local_store::load_local_store(app).map_err(|e| e.user_message)?;
+    let setting = store
+        .employee_settings
+        .iter()
+        .find(|s| s.daylite_contact_reference == input.employee_reference);
+    let calendar_url = primary_calendar_url(setting)?;
+    let day = parse_assignment_date(&input.date)?;
+    let session = load_caldav_session(&store)?;
+
+    redistribute_day(&session, &store, &calendar_url, day, &input.uids).await?;
+    Ok(())
 }
@@ -1,10 +1,13 @@
 use chrono::NaiveDate;
 
+use super::super::capacity::partial_absence_minutes;
 use super::super::types::{CalendarCellEvent, CalendarEventKind, RawVEvent};
+use crate::integrations::local_store::{AbsenceCategory, AbsenceCategoryRule};
 
 pub(crate) fn map_absence_raw_events_for_week(
     raw_events: Vec<RawVEvent>,
     week_start: NaiveDate,
+    category_rules: &[AbsenceCategoryRule],
 ) -> Vec<CalendarCellEvent> {
     let week_end = week_start + chrono::Duration::days(7);
     let mut result = Vec::new();
@@ -15,6 +18,7 @@ pub(crate) fn map_absence_raw_events_for_week(
             Err(_) => continue,
         };
 
+        let category = classify_absence(&raw.summary, &raw.categories, category_rules);
         let href = if raw.href.is_empty() {
             None
         } else {
@@ -37,10 +41,15 @@ pub(crate) fn map_absence_raw_events_for_week(
                     start_time: None,
                     end_time: None,
                     href: href.clone(),
+                    absence_category: Some(category),
+                    absence_minutes: None,
+                    order_index: None,
                 });
                 day += chrono::Duration::days(1);
             }
         } else {
+            let absence_minutes =
+                partial_absence_minutes(raw.start_time.as_deref(), raw.end_time.as_deref());
             result.push(CalendarCellEvent {
                 uid: raw.uid,
                 kind: CalendarEventKind::Absence,
@@ -51,6 +60,9 @@ pub(crate) fn map_absence_raw_events_for_week(
                 start_time: raw.start_time,
                 end_time: raw.end_time,
                 href,
+                absence_category: Some(category),
+                absence_minutes,
+                order_index: None,
             });
         }
     }
@@ -58,9 +70,110 @@ pub(crate) fn map_absence_raw_events_for_week(
     result
 }
 
+/// CATEGORIES is set deliberately by whoever entered the absence, so a CATEGORIES match in
+/// any rule beats a summary keyword match in an earlier rule.
+pub(crate) fn classify_absence(
+    summary: &str,
+    ical_categories: &[String],
+    rules: &[AbsenceCategoryRule],
+) -> AbsenceCategory {
+    let by_ical_category = rules.iter().find(|rule| {
+        rule.ical_categories.iter().any(|expected| {
+            ical_categories
+                .iter()
+                .any(|actual| actual.trim().eq_ignore_ascii_case(expected.trim()))
+        })
+    });
+    if let Some(rule) = by_ical_category {
+        return rule.category;
+    }
+
+    let summary = summary.to_lowercase();
+    rules
+        .iter()
+        .find(|rule| {
+            rule.summary_keywords.iter().any(|keyword| {
+                let keyword = keyword.trim().to_lowercase();
+                !keyword.is_empty() && summary.contains(&keyword)
+            })
+        })
+        .map(|rule| rule.category)
+        .unwrap_or(AbsenceCategory::Other)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::integrations::local_store::AbsenceSettings;
+
+    fn rules() -> Vec<AbsenceCategoryRule> {
+        AbsenceSettings::default().category_rules
+    }
+
+    fn categories(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn classifies_absences_by_summary_keyword_with_default_rules() {
+        let rules = rules();
+        let classify = |summary| classify_absence(summary, &[], &rules);
+
+        assert_eq!(classify("Urlaub"), AbsenceCategory::Vacation);
+        assert_eq!(
+            classify("Sonderurlaub Hochzeit"),
+            AbsenceCategory::SpecialLeave
+        );
+        assert_eq!(classify("Krankenstand"), AbsenceCategory::Sick);
+        assert_eq!(classify("krank"), AbsenceCategory::Sick);
+        assert_eq!(classify("Berufsschule"), AbsenceCategory::School);
+        assert_eq!(classify("Arzttermin"), AbsenceCategory::Other);
+    }
+
+    #[test]
+    fn ical_categories_take_precedence_over_summary_keywords() {
+        let category = classify_absence("Urlaub", &categories(&["krank"]), &rules());
+
+        assert_eq!(category, AbsenceCategory::Sick);
+    }
+
+    #[test]
+    fn custom_rules_replace_default_matching() {
+        let rules = vec![AbsenceCategoryRule {
+            category: AbsenceCategory::School,
+            summary_keywords: vec!["ÜBA".to_string()],
+            ical_categories: vec![],
+        }];
+
+        assert_eq!(
+            classify_absence("Lehrgang üba", &[], &rules),
+            AbsenceCategory::School
+        );
+        assert_eq!(
+            classify_absence("Urlaub", &[], &rules),
+            AbsenceCategory::Other
+        );
+    }
+
+    #[test]
+    fn expanded_multi_day_absence_carries_category_on_every_day() {
+        let raw = vec![RawVEvent {
+            uid: "abs-1".to_string(),
+            summary: "Urlaub".to_string(),
+            dtstart: "2026-04-27".to_string(),
+            dtend: Some(NaiveDate::from_ymd_opt(2026, 4, 29).unwrap()),
+            ..Default::default()
+        }];
+        let week_start = NaiveDate::from_ymd_opt(2026, 4, 27).unwrap();
+
+        let events = map_absence_raw_events_for_week(raw, week_start, &rules());
+
+        assert_eq!(events.len(), 2);
+        assert!(events
+            .iter()
+            .all(|e| e.absence_category == Some(AbsenceCategory::Vacation)));
+        assert!(events.iter().all(|e| e.absence_minutes.is_none()));
+    }
 
     #[test]
     fn absence_event_has_absence_kind_title_and_no_project_status() {
@@ -73,7 +186,7 @@ mod tests {
         };
         let week_start = NaiveDate::from_ymd_opt(2026, 4, 27).unwrap();
 
-        let events = map_absence_raw_events_for_week(vec![raw], week_start);
+        let events = map_absence_raw_events_for_week(vec![raw], week_start, &rules());
 
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].kind, CalendarEventKind::Absence);
@@ -99,7 +212,7 @@ mod tests {
             },
         ];
 
-        let events = map_absence_raw_events_for_week(raw, week_start);
+        let events = map_absence_raw_events_for_week(raw, week_start, &rules());
 
         assert_eq!(events.len(), 2);
         assert!(events.iter().all(|e| e.kind == CalendarEventKind::Absence));
@@ -109,7 +222,7 @@ mod tests {
     #[test]
     fn returns_empty_when_no_absence_raw_events() {
         let week_start = NaiveDate::from_ymd_opt(2026, 4, 27).unwrap();
-        let events = map_absence_raw_events_for_week(vec![], week_start);
+        let events = map_absence_raw_events_for_week(vec![], week_start, &rules());
         assert!(events.is_empty());
     }
 
@@ -118,7 +231,7 @@ mod tests {
         let raw: Vec<RawVEvent> = Vec::new();
         let week_start = NaiveDate::from_ymd_opt(2026, 4, 27).unwrap();
 
-        let events = map_absence_raw_events_for_week(raw, week_start);
+        let events = map_absence_raw_events_for_week(raw, week_start, &rules());
 
         assert!(events.is_empty());
     }
@@ -134,7 +247,7 @@ mod tests {
         }];
         let week_start = NaiveDate::from_ymd_opt(2026, 4, 27).unwrap();
 
-        let events = map_absence_raw_events_for_week(raw, week_start);
+        let events = map_absence_raw_events_for_week(raw, week_start, &rules());
 
         assert_eq!(events.len(), 5);
         assert_eq!(events[0].date, "2026-04-27");
@@ -154,7 +267,7 @@ mod tests {
         }];
         let week_start = NaiveDate::from_ymd_opt(2026, 4, 27).unwrap();
 
-        let events = map_absence_raw_events_for_week(raw, week_start);
+        let events = map_absence_raw_events_for_week(raw, week_start, &rules());
 
         assert_eq!(events.len(), 2);
         assert_eq!(events[0].date, "2026-04-27");
@@ -174,11 +287,12 @@ mod tests {
         }];
         let week_start = NaiveDate::from_ymd_opt(2026, 4, 27).unwrap();
 
-        let events = map_absence_raw_events_for_week(raw, week_start);
+        let events = map_absence_raw_events_for_week(raw, week_start, &rules());
 
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].date, "2026-04-28");
         assert_eq!(events[0].kind, CalendarEventKind::Absence);
+        assert_eq!(events[0].absence_minutes, Some(540));
     }
 
     #[test]
@@ -193,7 +307,7 @@ mod tests {
         }];
         let week_start = NaiveDate::from_ymd_opt(2026, 4, 27).unwrap();
 
-        let events = map_absence_raw_events_for_week(raw, week_start);
+        let events = map_absence_raw_events_for_week(raw, week_start, &rules());
 
         assert_eq!(events.len(), 1);
         assert_eq!(events[0].kind, CalendarEventKind::Absence);
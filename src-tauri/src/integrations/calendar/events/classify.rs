@@ -2,6 +2,11 @@ use super::super::types::{PendingEvent, RawVEvent};
 
 const DAYLITE_DESCRIPTION_PREFIX: &str = "daylite:";
 
+/// The DESCRIPTION line that links an lkr-planner event to its Daylite project.
+pub(crate) fn project_marker(project_ref: &str) -> String {
+    format!("{DAYLITE_DESCRIPTION_PREFIX}{project_ref}")
+}
+
 pub(crate) fn classify_event(event: RawVEvent) -> PendingEvent {
     let date = event.dtstart;
 
@@ -47,6 +52,8 @@ pub(crate) fn classify_event(event: RawVEvent) -> PendingEvent {
         start_time: event.start_time,
         end_time: event.end_time,
         href: event.href,
+        last_modified: event.last_modified,
+        order_index: event.order_index,
     }
 }
 
@@ -0,0 +1,263 @@
+use std::collections::{BTreeMap, HashSet};
+
+use super::super::types::{AssignmentConflict, AssignmentConflictKind, PendingEvent};
+
+/// Reports duplicate assignments (same day and project) and time overlaps between the
+/// assignments of a single employee. Pairs already reported as duplicates are not
+/// reported again as overlaps.
+pub(crate) fn find_assignment_conflicts(events: &[PendingEvent]) -> Vec<AssignmentConflict> {
+    // CalDAV servers occasionally redeliver the same event; that is not a conflict.
+    let mut seen_uids = HashSet::new();
+    let assignments: Vec<&PendingEvent> = events
+        .iter()
+        .filter(|e| e.project_ref.is_some())
+        .filter(|e| seen_uids.insert(e.uid.as_str()))
+        .collect();
+
+    let mut conflicts = Vec::new();
+    let mut duplicate_pairs: HashSet<(&str, &str)> = HashSet::new();
+
+    let mut by_day_and_project: BTreeMap<(&str, &str), Vec<&PendingEvent>> = BTreeMap::new();
+    for event in &assignments {
+        let project_ref = event.project_ref.as_deref().unwrap_or_default();
+        by_day_and_project
+            .entry((event.date.as_str(), project_ref))
+            .or_default()
+            .push(event);
+    }
+    for ((date, project_ref), group) in by_day_and_project {
+        if group.len() < 2 {
+            continue;
+        }
+        let mut uids: Vec<String> = group.iter().map(|e| e.uid.clone()).collect();
+        uids.sort();
+        for a in &group {
+            for b in &group {
+                duplicate_pairs.insert((a.uid.as_str(), b.uid.as_str()));
+            }
+        }
+        conflicts.push(AssignmentConflict {
+            kind: AssignmentConflictKind::Duplicate,
+            date: date.to_string(),
+            uids,
+            project_ref: Some(project_ref.to_string()),
+        });
+    }
+
+    for (i, a) in assignments.iter().enumerate() {
+        for b in &assignments[i + 1..] {
+            if a.date != b.date || duplicate_pairs.contains(&(a.uid.as_str(), b.uid.as_str())) {
+                continue;
+            }
+            if windows_overlap(a, b) {
+                let mut uids = vec![a.uid.clone(), b.uid.clone()];
+                uids.sort();
+                conflicts.push(AssignmentConflict {
+                    kind: AssignmentConflictKind::Overlap,
+                    date: a.date.clone(),
+                    uids,
+                    project_ref: None,
+                });
+            }
+        }
+    }
+
+    conflicts.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.uids.cmp(&b.uids)));
+    conflicts
+}
+
+/// Returns true if `events` already contain an assignment for `project_ref` on `date`,
+/// ignoring the event with `exclude_uid` (the one being updated).
+pub(crate) fn has_duplicate_assignment(
+    events: &[PendingEvent],
+    date: &str,
+    project_ref: &str,
+    exclude_uid: Option<&str>,
+) -> bool {
+    events.iter().any(|e| {
+        e.date == date
+            && e.project_ref.as_deref() == Some(project_ref)
+            && Some(e.uid.as_str()) != exclude_uid
+    })
+}
+
+/// Returns true if an assignment written on `date` into the `start_time`..`end_time`
+/// window would overlap another assignment of that day whose times stay as they are.
+/// Assignments with an href are re-slotted side by side after every write, so only those
+/// the re-slotting cannot rewrite can still collide.
+pub(crate) fn has_overlapping_assignment(
+    events: &[PendingEvent],
+    date: &str,
+    start_time: &str,
+    end_time: &str,
+    exclude_uid: Option<&str>,
+) -> bool {
+    events.iter().any(|e| {
+        e.date == date
+            && e.project_ref.is_some()
+            && e.href.is_empty()
+            && Some(e.uid.as_str()) != exclude_uid
+            && match (e.start_time.as_deref(), e.end_time.as_deref()) {
+                (Some(e_start), Some(e_end)) => e_start < end_time && start_time < e_end,
+                _ => false,
+            }
+    })
+}
+
+// HH:MM strings compare correctly as text. All-day events have no window and never overlap.
+fn windows_overlap(a: &PendingEvent, b: &PendingEvent) -> bool {
+    match (
+        a.start_time.as_deref(),
+        a.end_time.as_deref(),
+        b.start_time.as_deref(),
+        b.end_time.as_deref(),
+    ) {
+        (Some(a_start), Some(a_end), Some(b_start), Some(b_end)) => {
+            a_start < b_end && b_start < a_end
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assignment(
+        uid: &str,
+        date: &str,
+        project_ref: &str,
+        start: &str,
+        end: &str,
+    ) -> PendingEvent {
+        PendingEvent {
+            uid: uid.to_string(),
+            date: date.to_string(),
+            summary: "Projekt".to_string(),
+            project_ref: Some(project_ref.to_string()),
+            start_time: Some(start.to_string()),
+            end_time: Some(end.to_string()),
+            href: String::new(),
+            last_modified: None,
+            order_index: None,
+        }
+    }
+
+    #[test]
+    fn reports_duplicate_project_on_same_day_once() {
+        let events = vec![
+            assignment("b", "2026-05-04", "/v1/projects/1", "08:00", "16:00"),
+            assignment("a", "2026-05-04", "/v1/projects/1", "08:00", "16:00"),
+        ];
+
+        let conflicts = find_assignment_conflicts(&events);
+
+        assert_eq!(
+            conflicts,
+            vec![AssignmentConflict {
+                kind: AssignmentConflictKind::Duplicate,
+                date: "2026-05-04".to_string(),
+                uids: vec!["a".to_string(), "b".to_string()],
+                project_ref: Some("/v1/projects/1".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn reports_overlap_between_different_projects() {
+        let events = vec![
+            assignment("a", "2026-05-04", "/v1/projects/1", "08:00", "12:00"),
+            assignment("b", "2026-05-04", "/v1/projects/2", "11:00", "16:00"),
+            // Touching windows do not overlap.
+            assignment("c", "2026-05-04", "/v1/projects/3", "16:00", "18:00"),
+        ];
+
+        let conflicts = find_assignment_conflicts(&events);
+
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].kind, AssignmentConflictKind::Overlap);
+        assert_eq!(conflicts[0].uids, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(conflicts[0].project_ref, None);
+    }
+
+    #[test]
+    fn ignores_redelivered_events_bare_events_and_other_days() {
+        let mut bare = assignment("bare", "2026-05-04", "/v1/projects/1", "08:00", "16:00");
+        bare.project_ref = None;
+        let events = vec![
+            assignment("a", "2026-05-04", "/v1/projects/1", "08:00", "16:00"),
+            assignment("a", "2026-05-04", "/v1/projects/1", "08:00", "16:00"),
+            bare,
+            assignment("b", "2026-05-05", "/v1/projects/1", "08:00", "16:00"),
+        ];
+
+        assert!(find_assignment_conflicts(&events).is_empty());
+    }
+
+    #[test]
+    fn has_overlapping_assignment_only_counts_assignments_that_keep_their_times() {
+        let fixed = assignment("a", "2026-05-04", "/v1/projects/1", "08:00", "12:00");
+        let mut slotted = assignment("b", "2026-05-04", "/v1/projects/2", "08:00", "16:00");
+        slotted.href = "/calendars/b.ics".to_string();
+        let events = vec![fixed, slotted];
+
+        assert!(has_overlapping_assignment(
+            &events,
+            "2026-05-04",
+            "11:00",
+            "16:00",
+            None
+        ));
+        assert!(!has_overlapping_assignment(
+            &events,
+            "2026-05-04",
+            "12:00",
+            "16:00",
+            None
+        ));
+        assert!(!has_overlapping_assignment(
+            &events,
+            "2026-05-04",
+            "08:00",
+            "16:00",
+            Some("a")
+        ));
+        assert!(!has_overlapping_assignment(
+            &events,
+            "2026-05-05",
+            "08:00",
+            "16:00",
+            None
+        ));
+    }
+
+    #[test]
+    fn has_duplicate_assignment_ignores_the_event_being_updated() {
+        let events = vec![assignment(
+            "a",
+            "2026-05-04",
+            "/v1/projects/1",
+            "08:00",
+            "16:00",
+        )];
+
+        assert!(has_duplicate_assignment(
+            &events,
+            "2026-05-04",
+            "/v1/projects/1",
+            None
+        ));
+        assert!(!has_duplicate_assignment(
+            &events,
+            "2026-05-04",
+            "/v1/projects/1",
+            Some("a")
+        ));
+        assert!(!has_duplicate_assignment(
+            &events,
+            "2026-05-05",
+            "/v1/projects/1",
+            None
+        ));
+    }
+}
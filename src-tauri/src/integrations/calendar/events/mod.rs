@@ -1,9 +1,13 @@
 mod absences;
 mod classify;
+mod conflicts;
 mod order;
 mod resolve;
 
 pub(super) use absences::map_absence_raw_events_for_week;
-pub(super) use classify::classify_event;
+pub(super) use classify::{classify_event, project_marker};
+pub(super) use conflicts::{
+    find_assignment_conflicts, has_duplicate_assignment, has_overlapping_assignment,
+};
 pub(super) use order::sort_events_absences_first;
 pub(super) use resolve::resolve_event;
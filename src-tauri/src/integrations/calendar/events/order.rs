@@ -1,5 +1,7 @@
 use super::super::types::{CalendarCellEvent, CalendarEventKind};
 
+/// Orders a day's events: absences first, then by the planner's order index (unordered
+/// events last), then by start time.
 pub(crate) fn sort_events_absences_first(events: &mut [CalendarCellEvent]) {
     events.sort_by(|a, b| {
         let kind_order = |e: &CalendarCellEvent| {
@@ -9,7 +11,12 @@ pub(crate) fn sort_events_absences_first(events: &mut [CalendarCellEvent]) {
                 1u8
             }
         };
-        a.date.cmp(&b.date).then(kind_order(a).cmp(&kind_order(b)))
+        let position = |e: &CalendarCellEvent| (e.order_index.is_none(), e.order_index);
+        a.date
+            .cmp(&b.date)
+            .then(kind_order(a).cmp(&kind_order(b)))
+            .then(position(a).cmp(&position(b)))
+            .then_with(|| a.start_time.cmp(&b.start_time))
     });
 }
 
@@ -26,6 +33,9 @@ mod tests {
                 title: "Projekt".to_string(),
                 project_status: Some("in_progress".to_string()),
                 project_ref: Some("/v1/projects/1".to_string()),
+                absence_category: None,
+                absence_minutes: None,
+                order_index: None,
                 date: "2026-04-28".to_string(),
                 start_time: Some("09:00".to_string()),
                 end_time: Some("17:00".to_string()),
@@ -37,6 +47,9 @@ mod tests {
                 title: "Urlaub".to_string(),
                 project_status: None,
                 project_ref: None,
+                absence_category: None,
+                absence_minutes: None,
+                order_index: None,
                 date: "2026-04-28".to_string(),
                 start_time: None,
                 end_time: None,
@@ -59,6 +72,9 @@ mod tests {
                 title: "Blocker".to_string(),
                 project_status: None,
                 project_ref: None,
+                absence_category: None,
+                absence_minutes: None,
+                order_index: None,
                 date: "2026-04-28".to_string(),
                 start_time: Some("10:00".to_string()),
                 end_time: None,
@@ -70,6 +86,9 @@ mod tests {
                 title: "Urlaub".to_string(),
                 project_status: None,
                 project_ref: None,
+                absence_category: None,
+                absence_minutes: None,
+                order_index: None,
                 date: "2026-04-28".to_string(),
                 start_time: None,
                 end_time: None,
@@ -92,6 +111,9 @@ mod tests {
                 title: "Projekt".to_string(),
                 project_status: Some("in_progress".to_string()),
                 project_ref: Some("/v1/projects/1".to_string()),
+                absence_category: None,
+                absence_minutes: None,
+                order_index: None,
                 date: "2026-04-27".to_string(),
                 start_time: Some("09:00".to_string()),
                 end_time: None,
@@ -103,6 +125,9 @@ mod tests {
                 title: "Urlaub".to_string(),
                 project_status: None,
                 project_ref: None,
+                absence_category: None,
+                absence_minutes: None,
+                order_index: None,
                 date: "2026-04-28".to_string(),
                 start_time: None,
                 end_time: None,
@@ -115,4 +140,32 @@ mod tests {
         assert_eq!(events[0].date, "2026-04-27");
         assert_eq!(events[1].date, "2026-04-28");
     }
+
+    #[test]
+    fn assignments_follow_the_order_index_within_a_day() {
+        let assignment = |uid: &str, start: &str, order_index: Option<u32>| CalendarCellEvent {
+            uid: uid.to_string(),
+            kind: CalendarEventKind::Assignment,
+            title: "Projekt".to_string(),
+            project_status: Some("in_progress".to_string()),
+            project_ref: Some("/v1/projects/1".to_string()),
+            absence_category: None,
+            absence_minutes: None,
+            order_index,
+            date: "2026-04-28".to_string(),
+            start_time: Some(start.to_string()),
+            end_time: None,
+            href: None,
+        };
+        let mut events = vec![
+            assignment("unordered", "07:00", None),
+            assignment("second", "08:00", Some(1)),
+            assignment("first", "12:00", Some(0)),
+        ];
+
+        sort_events_absences_first(&mut events);
+
+        let uids: Vec<&str> = events.iter().map(|e| e.uid.as_str()).collect();
+        assert_eq!(uids, vec!["first", "second", "unordered"]);
+    }
 }
@@ -16,6 +16,8 @@ pub(crate) fn resolve_event(
         start_time,
         end_time,
         href,
+        last_modified: _,
+        order_index,
     } = pending;
 
     let href = if href.is_empty() { None } else { Some(href) };
@@ -27,6 +29,9 @@ pub(crate) fn resolve_event(
             title: summary,
             project_status: None,
             project_ref: None,
+            absence_category: None,
+            absence_minutes: None,
+            order_index,
             date,
             start_time,
             end_time,
@@ -41,6 +46,9 @@ pub(crate) fn resolve_event(
             title: cached.name.clone(),
             project_status: Some(cached.status.clone()),
             project_ref: Some(project_ref.clone()),
+            absence_category: None,
+            absence_minutes: None,
+            order_index,
             date,
             start_time,
             end_time,
@@ -55,6 +63,9 @@ pub(crate) fn resolve_event(
             title: name.clone(),
             project_status: Some(status.clone()),
             project_ref: Some(project_ref.clone()),
+            absence_category: None,
+            absence_minutes: None,
+            order_index,
             date,
             start_time,
             end_time,
@@ -68,6 +79,9 @@ pub(crate) fn resolve_event(
         title: format!("Beschreibung für {} konnte nicht abgerufen werden", summary),
         project_status: None,
         project_ref: Some(project_ref),
+        absence_category: None,
+        absence_minutes: None,
+        order_index,
         date,
         start_time,
         end_time,
@@ -92,6 +106,8 @@ mod tests {
             start_time: None,
             end_time: None,
             href: String::new(),
+            last_modified: None,
+            order_index: None,
         };
         let cache = DayliteCache {
             last_synced_at: None,
@@ -99,8 +115,10 @@ mod tests {
                 reference: "/v1/projects/3001".to_string(),
                 name: "Projekt Nord".to_string(),
                 status: "in_progress".to_string(),
+                ..DayliteProjectCacheEntry::default()
             }],
             contacts: vec![],
+            ..DayliteCache::default()
         };
         let api_results = HashMap::new();
 
@@ -122,6 +140,8 @@ mod tests {
             start_time: None,
             end_time: None,
             href: String::new(),
+            last_modified: None,
+            order_index: None,
         };
         let cache = DayliteCache::default();
         let mut api_results = HashMap::new();
@@ -147,6 +167,8 @@ mod tests {
             start_time: None,
             end_time: None,
             href: String::new(),
+            last_modified: None,
+            order_index: None,
         };
         let cache = DayliteCache::default();
         let mut api_results = HashMap::new();
@@ -171,6 +193,8 @@ mod tests {
             start_time: None,
             end_time: None,
             href: String::new(),
+            last_modified: None,
+            order_index: None,
         };
         let cache = DayliteCache::default();
         let api_results = HashMap::new();
@@ -198,8 +222,10 @@ mod tests {
                 reference: "/v1/projects/3001".to_string(),
                 name: "Projekt Nord".to_string(),
                 status: "in_progress".to_string(),
+                ..DayliteProjectCacheEntry::default()
             }],
             contacts: vec![],
+            ..DayliteCache::default()
         };
 
         let pending = classify_event(event);
@@ -2,20 +2,30 @@ use icalendar::{Calendar, CalendarComponent, CalendarDateTime, Component, DatePe
 
 use super::types::RawVEvent;
 
+/// Non-standard property carrying an assignment's position within its day.
+pub(super) const ORDER_PROPERTY: &str = "X-LKR-ORDER";
+
+/// `start_time` and `end_time` are HH:MM in local floating time.
 pub(crate) fn build_ical_payload(
     uid: &str,
     date: &str,
     summary: &str,
     project_ref: &str,
+    start_time: &str,
+    end_time: &str,
+    order_index: Option<u32>,
 ) -> String {
     let compact = date.replace('-', "");
-    let dtstart = format!("{compact}T080000");
-    let dtend = format!("{compact}T160000");
+    let dtstart = format!("{compact}T{}00", start_time.replace(':', ""));
+    let dtend = format!("{compact}T{}00", end_time.replace(':', ""));
     let dtstamp = chrono::Utc::now().format("%Y%m%dT%H%M%SZ");
     let summary = escape_ical_text(summary);
     let description = escape_ical_text(&format!("daylite:{project_ref}"));
+    let order = order_index
+        .map(|index| format!("{ORDER_PROPERTY}:{index}\r\n"))
+        .unwrap_or_default();
     format!(
-        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//lkr-planner//EN\r\nBEGIN:VEVENT\r\nUID:{uid}\r\nDTSTAMP:{dtstamp}\r\nDTSTART:{dtstart}\r\nDTEND:{dtend}\r\nSUMMARY:{summary}\r\nDESCRIPTION:{description}\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n"
+        "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//lkr-planner//EN\r\nBEGIN:VEVENT\r\nUID:{uid}\r\nDTSTAMP:{dtstamp}\r\nDTSTART:{dtstart}\r\nDTEND:{dtend}\r\nSUMMARY:{summary}\r\nDESCRIPTION:{description}\r\n{order}END:VEVENT\r\nEND:VCALENDAR\r\n"
     )
 }
 
@@ -28,7 +38,7 @@ fn escape_ical_text(value: &str) -> String {
         .replace(['\n', '\r'], "\\n")
 }
 
-pub(super) fn parse_ical_events(ical_text: &str) -> Result<Vec<RawVEvent>, String> {
+pub(crate) fn parse_ical_events(ical_text: &str) -> Result<Vec<RawVEvent>, String> {
     let calendar: Calendar = ical_text
         .parse()
         .map_err(|e| format!("iCal-Daten konnten nicht gelesen werden: {e:?}"))?;
@@ -73,6 +83,19 @@ pub(super) fn parse_ical_events(ical_text: &str) -> Result<Vec<RawVEvent>, Strin
                 start_time,
                 end_time,
                 href: String::new(), // populated by parse_caldav_report from d:href
+                categories: event
+                    .multi_properties()
+                    .get("CATEGORIES")
+                    .into_iter()
+                    .flatten()
+                    .flat_map(|property| property.value().split(','))
+                    .map(|value| value.trim().to_string())
+                    .filter(|value| !value.is_empty())
+                    .collect(),
+                last_modified: event.get_last_modified().or_else(|| event.get_timestamp()),
+                order_index: event
+                    .property_value(ORDER_PROPERTY)
+                    .and_then(|value| value.trim().parse().ok()),
             })
         })
         .collect();
@@ -146,6 +169,58 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_ical_events_prefers_last_modified_over_dtstamp() {
+        let ical = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:ev-1\r\nDTSTAMP:20260101T080000Z\r\nLAST-MODIFIED:20260301T120000Z\r\nDTSTART:20260126T080000\r\nEND:VEVENT\r\nBEGIN:VEVENT\r\nUID:ev-2\r\nDTSTAMP:20260101T080000Z\r\nDTSTART:20260126T080000\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let events = parse_ical_events(ical).unwrap();
+
+        assert_eq!(
+            events[0].last_modified.map(|t| t.to_rfc3339()),
+            Some("2026-03-01T12:00:00+00:00".to_string())
+        );
+        assert_eq!(
+            events[1].last_modified.map(|t| t.to_rfc3339()),
+            Some("2026-01-01T08:00:00+00:00".to_string())
+        );
+    }
+
+    #[test]
+    fn parse_ical_events_collects_categories_from_all_lines() {
+        let ical = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:abs-1\r\nSUMMARY:Abwesend\r\nCATEGORIES:Urlaub,Privat\r\nCATEGORIES:Team\r\nDTSTART;VALUE=DATE:20260427\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let events = parse_ical_events(ical).unwrap();
+
+        assert_eq!(events[0].categories, vec!["Urlaub", "Privat", "Team"]);
+    }
+
+    #[test]
+    fn order_index_round_trips_through_payload() {
+        let payload = build_ical_payload(
+            "uid-o",
+            "2026-05-06",
+            "Projekt",
+            "/v1/projects/42",
+            "08:00",
+            "12:00",
+            Some(2),
+        );
+
+        let events = parse_ical_events(&payload).unwrap();
+
+        assert!(payload.contains("X-LKR-ORDER:2\r\n"));
+        assert_eq!(events[0].order_index, Some(2));
+    }
+
+    #[test]
+    fn unparsable_order_index_is_ignored() {
+        let ical = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:ev-1\r\nX-LKR-ORDER:first\r\nDTSTART:20260126T080000\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        let events = parse_ical_events(ical).unwrap();
+
+        assert_eq!(events[0].order_index, None);
+    }
+
     #[test]
     fn malformed_ical_text_returns_error() {
         let result = parse_ical_events("this is definitely not valid ical");
@@ -159,6 +234,9 @@ mod tests {
             "2026-05-06",
             "Mein Projekt",
             "/v1/projects/42",
+            "08:00",
+            "16:00",
+            None,
         );
 
         assert!(payload.contains("BEGIN:VCALENDAR"), "missing VCALENDAR");
@@ -175,9 +253,32 @@ mod tests {
         assert!(payload.contains("END:VCALENDAR"), "missing END:VCALENDAR");
     }
 
+    #[test]
+    fn build_ical_payload_uses_given_time_window() {
+        let payload = build_ical_payload(
+            "uid-3",
+            "2026-05-06",
+            "Test",
+            "/v1/projects/1",
+            "12:30",
+            "16:00",
+            None,
+        );
+        assert!(payload.contains("DTSTART:20260506T123000\r\n"));
+        assert!(payload.contains("DTEND:20260506T160000\r\n"));
+    }
+
     #[test]
     fn build_ical_payload_uses_floating_local_time_no_z_suffix() {
-        let payload = build_ical_payload("uid-2", "2026-12-31", "Test", "/v1/projects/1");
+        let payload = build_ical_payload(
+            "uid-2",
+            "2026-12-31",
+            "Test",
+            "/v1/projects/1",
+            "08:00",
+            "16:00",
+            None,
+        );
         assert!(
             payload.contains("DTSTART:20261231T080000\r\n"),
             "DTSTART must not have Z suffix"
@@ -195,6 +296,9 @@ mod tests {
             "2026-05-06",
             "Müller, Söhne; Bau \\ Test",
             "/v1/projects/42",
+            "08:00",
+            "16:00",
+            None,
         );
         assert!(
             payload.contains("SUMMARY:Müller\\, Söhne\\; Bau \\\\ Test"),
@@ -204,8 +308,15 @@ mod tests {
 
     #[test]
     fn build_ical_payload_escapes_newline_in_summary_to_literal() {
-        let payload =
-            build_ical_payload("uid-nl", "2026-05-06", "Zeile1\nZeile2", "/v1/projects/42");
+        let payload = build_ical_payload(
+            "uid-nl",
+            "2026-05-06",
+            "Zeile1\nZeile2",
+            "/v1/projects/42",
+            "08:00",
+            "16:00",
+            None,
+        );
         assert!(
             payload.contains("SUMMARY:Zeile1\\nZeile2"),
             "newline must become the two-char escape, got: {payload}"
@@ -220,7 +331,15 @@ mod tests {
     fn build_ical_payload_keeps_path_separators_in_description() {
         // Forward slashes are not RFC 5545 special characters and must survive so the
         // daylite: project reference round-trips through classification on read-back.
-        let payload = build_ical_payload("uid-d", "2026-05-06", "Projekt", "/v1/projects/42");
+        let payload = build_ical_payload(
+            "uid-d",
+            "2026-05-06",
+            "Projekt",
+            "/v1/projects/42",
+            "08:00",
+            "16:00",
+            None,
+        );
         assert!(
             payload.contains("DESCRIPTION:daylite:/v1/projects/42"),
             "got: {payload}"
@@ -0,0 +1,128 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::commands::load_caldav_session;
+use super::events::find_assignment_conflicts;
+use super::scan::{scan_primary_calendars, EmployeeScan};
+use super::types::{EmployeeAssignmentConflicts, EmployeeScanError};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WeekConflictReport {
+    pub employees: Vec<EmployeeAssignmentConflicts>,
+    // Employees whose calendar could not be read; their conflicts are not listed.
+    pub employee_errors: Vec<EmployeeScanError>,
+}
+
+/// Reports duplicate and overlapping assignments per employee for the week starting at
+/// `week_start`. Employees without conflicts are omitted.
+#[tauri::command]
+#[specta::specta]
+pub async fn lint_week_assignments(
+    app: tauri::AppHandle,
+    week_start: String,
+) -> Result<WeekConflictReport, String> {
+    let week_start_date = NaiveDate::parse_from_str(&week_start, "%Y-%m-%d")
+        .map_err(|_| format!("Ungültiges Wochenstartdatum: {week_start}"))?;
+    let store =
+        crate::integrations::local_store::load_local_store(app).map_err(|e| e.user_message)?;
+    let session = load_caldav_session(&store)?;
+
+    let scans = scan_primary_calendars(
+        &store,
+        &session,
+        week_start_date,
+        week_start_date + chrono::Duration::days(7),
+        None,
+    )
+    .await;
+
+    Ok(collect_week_conflicts(scans))
+}
+
+fn collect_week_conflicts(scans: Vec<EmployeeScan>) -> WeekConflictReport {
+    let mut report = WeekConflictReport {
+        employees: Vec::new(),
+        employee_errors: Vec::new(),
+    };
+    for scan in scans {
+        match scan.result {
+            Ok(events) => {
+                let conflicts = find_assignment_conflicts(&events);
+                if !conflicts.is_empty() {
+                    report.employees.push(EmployeeAssignmentConflicts {
+                        employee_reference: scan.employee_reference,
+                        conflicts,
+                    });
+                }
+            }
+            Err(error) => report.employee_errors.push(EmployeeScanError {
+                employee_reference: scan.employee_reference,
+                error,
+            }),
+        }
+    }
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::calendar::types::{AssignmentConflictKind, PendingEvent};
+
+    fn assignment(uid: &str, project_ref: &str) -> PendingEvent {
+        PendingEvent {
+            uid: uid.to_string(),
+            date: "2026-05-04".to_string(),
+            summary: "Projekt".to_string(),
+            project_ref: Some(project_ref.to_string()),
+            start_time: Some("08:00".to_string()),
+            end_time: Some("16:00".to_string()),
+            href: String::new(),
+            last_modified: None,
+            order_index: None,
+        }
+    }
+
+    fn scan(employee: &str, result: Result<Vec<PendingEvent>, String>) -> EmployeeScan {
+        EmployeeScan {
+            employee_reference: employee.to_string(),
+            result,
+        }
+    }
+
+    #[test]
+    fn lists_only_employees_with_conflicts_and_reports_failed_calendars() {
+        let scans = vec![
+            scan(
+                "/v1/contacts/1",
+                Ok(vec![
+                    assignment("a", "/v1/projects/1"),
+                    assignment("b", "/v1/projects/1"),
+                ]),
+            ),
+            scan(
+                "/v1/contacts/2",
+                Ok(vec![assignment("c", "/v1/projects/1")]),
+            ),
+            scan("/v1/contacts/3", Err("HTTP 500".to_string())),
+        ];
+
+        let report = collect_week_conflicts(scans);
+
+        assert_eq!(report.employees.len(), 1);
+        assert_eq!(report.employees[0].employee_reference, "/v1/contacts/1");
+        assert_eq!(
+            report.employees[0].conflicts[0].kind,
+            AssignmentConflictKind::Duplicate
+        );
+        assert_eq!(
+            report.employee_errors,
+            vec![EmployeeScanError {
+                employee_reference: "/v1/contacts/3".to_string(),
+                error: "HTTP 500".to_string(),
+            }]
+        );
+    }
+}
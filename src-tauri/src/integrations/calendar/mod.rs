@@ -1,5 +1,13 @@
 mod caldav;
+mod capacity;
 pub mod commands;
 mod events;
-mod ical;
+pub(crate) mod ical;
+pub mod lint;
+pub mod orphans;
+mod scan;
+mod slots;
+pub mod staffing;
+pub mod timeline;
+pub mod title_sync;
 pub mod types;
@@ -0,0 +1,391 @@
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeSet, HashMap};
+
+use super::caldav::{
+    calendar_url_for_href, delete_assignment_core, update_assignment_core, AssignmentWrite,
+};
+use super::capacity::working_window_times;
+use super::commands::{assignment_uid, load_caldav_session, model_for_calendar};
+use super::scan::{scan_primary_calendars, EmployeeScan, MAX_SCAN_RANGE_DAYS};
+use super::slots::{assignment_placement, redistribute_days_after_write};
+use super::types::{AssignmentBulkResult, AssignmentWriteFailure, EmployeeScanError};
+use crate::integrations::daylite::assignment_mirror::{
+    spawn_assignment_mirror, MirroredAssignment,
+};
+use crate::integrations::daylite::projects::{
+    fetch_project_records, PlanningProjectRecord, PlanningProjectStatus,
+};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OrphanReason {
+    Done,
+    Cancelled,
+    Abandoned,
+    Deleted,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanedAssignment {
+    pub employee_reference: String,
+    pub uid: String,
+    pub href: String,
+    pub date: String,
+    pub title: String,
+    pub project_ref: String,
+    pub reason: OrphanReason,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct OrphanScanReport {
+    pub assignments: Vec<OrphanedAssignment>,
+    // Employees whose calendar could not be read; their orphans are not listed.
+    pub employee_errors: Vec<EmployeeScanError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RelinkAssignmentTarget {
+    pub href: String,
+    pub uid: String,
+    pub date: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type)]
+#[serde(rename_all = "camelCase")]
+pub struct RelinkAssignmentsInput {
+    pub assignments: Vec<RelinkAssignmentTarget>,
+    pub project_ref: String,
+    pub project_name: String,
+}
+
+/// Lists upcoming assignments whose Daylite project is closed or no longer exists,
+/// starting today and covering `weeks` weeks.
+#[tauri::command]
+#[specta::specta]
+pub async fn scan_orphaned_assignments(
+    app: tauri::AppHandle,
+    weeks: u32,
+) -> Result<OrphanScanReport, String> {
+    let max_weeks = (MAX_SCAN_RANGE_DAYS / 7) as u32;
+    if weeks == 0 || weeks > max_weeks {
+        return Err(format!(
+            "Die Anzahl der Wochen muss zwischen 1 und {max_weeks} liegen."
+        ));
+    }
+    let range_start = chrono::Local::now().date_naive();
+    let range_end = range_start + chrono::Duration::weeks(i64::from(weeks));
+
+    let store = crate::integrations::local_store::load_local_store(app.clone())
+        .map_err(|e| e.user_message)?;
+    let session = load_caldav_session(&store)?;
+
+    let scans = scan_primary_calendars(&store, &session, range_start, range_end, None).await;
+    let project_refs: Vec<String> = scans
+        .iter()
+        .filter_map(|scan| scan.result.as_ref().ok())
+        .flatten()
+        .filter_map(|event| event.project_ref.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    // Without a reliable Daylite answer every assignment would look orphaned, so the
+    // scan fails instead of falling back to the cache.
+    let records = fetch_project_records(app, &project_refs)
+        .await
+        .map_err(|e| e.user_message)?;
+
+    let (assignments, employee_errors) = collect_orphans(scans, &records);
+    Ok(OrphanScanReport {
+        assignments,
+        employee_errors,
+    })
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn delete_orphaned_assignments(
+    app: tauri::AppHandle,
+    hrefs: Vec<String>,
+) -> Result<AssignmentBulkResult, String> {
+    let store =
+        crate::integrations::local_store::load_local_store(app).map_err(|e| e.user_message)?;
+    let session = load_caldav_session(&store)?;
+
+    let mut result = AssignmentBulkResult::default();
+    let mut touched_days = BTreeSet::new();
+    for href in hrefs {
+        let day = assignment_placement(&session, &href)
+            .await
+            .map(|placement| placement.day);
+        match delete_assignment_core(&session, &href).await {
+            Ok(()) => {
+                if let (Some(day), Ok(calendar_url)) =
+                    (day, calendar_url_for_href(&href, &session.base_url))
+                {
+                    touched_days.insert((calendar_url, day));
+                }
+                if let Some(uid) = assignment_uid(&href) {
+                    spawn_assignment_mirror(&store, uid.to_string(), None);
+                }
+                result.succeeded.push(href);
+            }
+            Err(error) => result.failed.push(AssignmentWriteFailure { href, error }),
+        }
+    }
+    redistribute_days_after_write(&session, &store, touched_days).await;
+    Ok(result)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn relink_orphaned_assignments(
+    app: tauri::AppHandle,
+    input: RelinkAssignmentsInput,
+) -> Result<AssignmentBulkResult, String> {
+    if input.project_ref.trim().is_empty() {
+        return Err("Projektreferenz fehlt.".to_string());
+    }
+    let store =
+        crate::integrations::local_store::load_local_store(app).map_err(|e| e.user_message)?;
+    let session = load_caldav_session(&store)?;
+
+    let mut result = AssignmentBulkResult::default();
+    let mut touched_days = BTreeSet::new();
+    for target in input.assignments {
+        let calendar_url = calendar_url_for_href(&target.href, &session.base_url).ok();
+        let model = calendar_url
+            .as_deref()
+            .map(|calendar_url| model_for_calendar(&store, calendar_url))
+            .unwrap_or(&store.default_working_time_model);
+        let day = NaiveDate::parse_from_str(&target.date, "%Y-%m-%d").ok();
+        let (start_time, end_time) = working_window_times(model);
+        // The day stays the same, so the assignment keeps its place in it.
+        let order_index = assignment_placement(&session, &target.href)
+            .await
+            .and_then(|placement| placement.order_index);
+        let write = AssignmentWrite {
+            date: target.date,
+            project_ref: input.project_ref.clone(),
+            project_name: input.project_name.clone(),
+            start_time,
+            end_time,
+            order_index,
+        };
+        match update_assignment_core(&session, &target.href, &target.uid, &write).await {
+            Ok(()) => {
+                if let (Some(calendar_url), Some(day)) = (calendar_url, day) {
+                    // The plan line still names the old project until it is mirrored again.
+                    if let Some(employee) = store.employee_for_primary_calendar(&calendar_url) {
+                        let assignment = MirroredAssignment {
+                            date: day,
+                            project_ref: input.project_ref.clone(),
+                            employee_reference: employee.daylite_contact_reference.clone(),
+                        };
+                        spawn_assignment_mirror(&store, target.uid, Some(assignment));
+                    }
+                    touched_days.insert((calendar_url, day));
+                }
+                result.succeeded.push(target.href);
+            }
+            Err(error) => result.failed.push(AssignmentWriteFailure {
+                href: target.href,
+                error,
+            }),
+        }
+    }
+    // Relinking rewrites the whole event, so the day's slots have to be restored.
+    redistribute_days_after_write(&session, &store, touched_days).await;
+    Ok(result)
+}
+
+fn orphan_reason(record: Option<&PlanningProjectRecord>) -> Option<OrphanReason> {
+    let Some(record) = record else {
+        return Some(OrphanReason::Deleted);
+    };
+    match record.status {
+        PlanningProjectStatus::Done => Some(OrphanReason::Done),
+        PlanningProjectStatus::Cancelled => Some(OrphanReason::Cancelled),
+        PlanningProjectStatus::Abandoned => Some(OrphanReason::Abandoned),
+        PlanningProjectStatus::NewStatus
+        | PlanningProjectStatus::InProgress
+        | PlanningProjectStatus::Deferred => None,
+    }
+}
+
+fn collect_orphans(
+    scans: Vec<EmployeeScan>,
+    records: &HashMap<String, Option<PlanningProjectRecord>>,
+) -> (Vec<OrphanedAssignment>, Vec<EmployeeScanError>) {
+    let mut orphans = Vec::new();
+    let mut errors = Vec::new();
+
+    for scan in scans {
+        let events = match scan.result {
+            Ok(events) => events,
+            Err(error) => {
+                errors.push(EmployeeScanError {
+                    employee_reference: scan.employee_reference,
+                    error,
+                });
+                continue;
+            }
+        };
+
+        for event in events {
+            let Some(project_ref) = event.project_ref else {
+                continue;
+            };
+            // Refs missing from the lookup were never queried; they are not proof of deletion.
+            let Some(record) = records.get(&project_ref) else {
+                continue;
+            };
+            // Without an href the event can neither be deleted nor relinked.
+            if event.href.is_empty() {
+                continue;
+            }
+            if let Some(reason) = orphan_reason(record.as_ref()) {
+                orphans.push(OrphanedAssignment {
+                    employee_reference: scan.employee_reference.clone(),
+                    uid: event.uid,
+                    href: event.href,
+                    date: event.date,
+                    title: event.summary,
+                    project_ref,
+                    reason,
+                });
+            }
+        }
+    }
+
+    orphans.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| a.employee_reference.cmp(&b.employee_reference))
+            .then_with(|| a.uid.cmp(&b.uid))
+    });
+    (orphans, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::calendar::types::PendingEvent;
+
+    fn assignment(uid: &str, date: &str, project_ref: &str) -> PendingEvent {
+        PendingEvent {
+            uid: uid.to_string(),
+            date: date.to_string(),
+            summary: "Projekt".to_string(),
+            project_ref: Some(project_ref.to_string()),
+            start_time: Some("08:00".to_string()),
+            end_time: Some("16:00".to_string()),
+            href: format!("/calendars/{uid}.ics"),
+            last_modified: None,
+            order_index: None,
+        }
+    }
+
+    fn scan(employee: &str, result: Result<Vec<PendingEvent>, String>) -> EmployeeScan {
+        EmployeeScan {
+            employee_reference: employee.to_string(),
+            result,
+        }
+    }
+
+    fn record(reference: &str, status: PlanningProjectStatus) -> PlanningProjectRecord {
+        PlanningProjectRecord {
+            reference: reference.to_string(),
+            name: "Projekt".to_string(),
+            status,
+            category: None,
+            keywords: vec![],
+            due: None,
+            started: None,
+            completed: None,
+            create_date: None,
+            modify_date: None,
+        }
+    }
+
+    #[test]
+    fn orphan_reason_flags_closed_and_deleted_projects_only() {
+        let reason = |status| orphan_reason(Some(&record("/v1/projects/1", status)));
+
+        assert_eq!(
+            reason(PlanningProjectStatus::Done),
+            Some(OrphanReason::Done)
+        );
+        assert_eq!(
+            reason(PlanningProjectStatus::Cancelled),
+            Some(OrphanReason::Cancelled)
+        );
+        assert_eq!(
+            reason(PlanningProjectStatus::Abandoned),
+            Some(OrphanReason::Abandoned)
+        );
+        assert_eq!(reason(PlanningProjectStatus::NewStatus), None);
+        assert_eq!(reason(PlanningProjectStatus::InProgress), None);
+        assert_eq!(reason(PlanningProjectStatus::Deferred), None);
+        assert_eq!(orphan_reason(None), Some(OrphanReason::Deleted));
+    }
+
+    #[test]
+    fn collect_orphans_lists_assignments_of_closed_projects_sorted_by_date() {
+        let scans = vec![
+            scan(
+                "/v1/contacts/1",
+                Ok(vec![
+                    assignment("a-1", "2026-05-06", "/v1/projects/1"),
+                    assignment("a-2", "2026-05-04", "/v1/projects/2"),
+                    assignment("a-3", "2026-05-05", "/v1/projects/3"),
+                ]),
+            ),
+            scan("/v1/contacts/2", Err("HTTP 500".to_string())),
+        ];
+        let mut records = HashMap::new();
+        records.insert(
+            "/v1/projects/1".to_string(),
+            Some(record("/v1/projects/1", PlanningProjectStatus::Done)),
+        );
+        records.insert("/v1/projects/2".to_string(), None);
+        records.insert(
+            "/v1/projects/3".to_string(),
+            Some(record("/v1/projects/3", PlanningProjectStatus::InProgress)),
+        );
+
+        let (orphans, errors) = collect_orphans(scans, &records);
+
+        assert_eq!(orphans.len(), 2);
+        assert_eq!(orphans[0].uid, "a-2");
+        assert_eq!(orphans[0].reason, OrphanReason::Deleted);
+        assert_eq!(orphans[1].uid, "a-1");
+        assert_eq!(orphans[1].reason, OrphanReason::Done);
+        assert_eq!(orphans[1].href, "/calendars/a-1.ics");
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn collect_orphans_skips_unqueried_refs_and_events_without_href() {
+        let mut without_href = assignment("a-1", "2026-05-04", "/v1/projects/1");
+        without_href.href = String::new();
+        let scans = vec![scan(
+            "/v1/contacts/1",
+            Ok(vec![
+                without_href,
+                assignment("a-2", "2026-05-04", "/v1/projects/99"),
+            ]),
+        )];
+        let mut records = HashMap::new();
+        records.insert("/v1/projects/1".to_string(), None);
+
+        let (orphans, _) = collect_orphans(scans, &records);
+
+        assert!(orphans.is_empty());
+    }
+}
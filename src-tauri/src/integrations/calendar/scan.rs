@@ -0,0 +1,147 @@
+use chrono::NaiveDate;
+
+use super::caldav::{
+    fetch_calendar_events_in_range, fetch_calendar_events_matching, CaldavSession,
+};
+use super::events::{classify_event, project_marker};
+use super::types::PendingEvent;
+use crate::integrations::local_store::LocalStore;
+
+/// Upper bound for range scans so a typo in a year cannot trigger a REPORT over decades.
+pub(super) const MAX_SCAN_RANGE_DAYS: i64 = 366;
+
+pub(super) struct EmployeeScan {
+    pub(super) employee_reference: String,
+    pub(super) result: Result<Vec<PendingEvent>, String>,
+}
+
+/// Parses an inclusive `YYYY-MM-DD` range and returns it with an exclusive end date.
+pub(super) fn parse_scan_range(
+    range_start: &str,
+    range_end: &str,
+) -> Result<(NaiveDate, NaiveDate), String> {
+    let start = NaiveDate::parse_from_str(range_start, "%Y-%m-%d")
+        .map_err(|_| format!("Ungültiges Startdatum: {range_start}"))?;
+    let end = NaiveDate::parse_from_str(range_end, "%Y-%m-%d")
+        .map_err(|_| format!("Ungültiges Enddatum: {range_end}"))?;
+
+    if end < start {
+        return Err("Das Enddatum darf nicht vor dem Startdatum liegen.".to_string());
+    }
+    if (end - start).num_days() >= MAX_SCAN_RANGE_DAYS {
+        return Err(format!(
+            "Der Zeitraum darf höchstens {MAX_SCAN_RANGE_DAYS} Tage umfassen."
+        ));
+    }
+
+    Ok((start, end + chrono::Duration::days(1)))
+}
+
+/// Fetches and classifies the primary calendar of every configured employee.
+/// Absence calendars are never scanned; events outside the range are dropped.
+/// With `project_ref`, only assignments for that project are kept and the server is
+/// asked to pre-filter by the project marker.
+pub(super) async fn scan_primary_calendars(
+    store: &LocalStore,
+    session: &CaldavSession,
+    range_start: NaiveDate,
+    range_end: NaiveDate,
+    project_ref: Option<&str>,
+) -> Vec<EmployeeScan> {
+    let marker = project_ref.map(project_marker);
+    let scan_futures: Vec<_> = store
+        .employee_settings
+        .iter()
+        .filter_map(|setting| {
+            let calendar_url = setting
+                .zep_primary_calendar
+                .as_deref()
+                .filter(|u| !u.is_empty())
+                .map(str::to_string)?;
+            let employee_reference = setting.daylite_contact_reference.clone();
+
+            let marker = marker.as_deref();
+            Some(async move {
+                let raw_events = match marker {
+                    Some(marker) => {
+                        fetch_calendar_events_matching(
+                            session,
+                            &calendar_url,
+                            range_start,
+                            range_end,
+                            marker,
+                        )
+                        .await
+                    }
+                    None => {
+                        fetch_calendar_events_in_range(
+                            session,
+                            &calendar_url,
+                            range_start,
+                            range_end,
+                        )
+                        .await
+                    }
+                };
+                let result = raw_events.map(|raw_events| {
+                    raw_events
+                        .into_iter()
+                        .map(classify_event)
+                        .filter(|event| is_within_range(&event.date, range_start, range_end))
+                        // The server-side text-match is a substring match (and may be skipped
+                        // entirely), so "/v1/projects/1" would also match "/v1/projects/12".
+                        .filter(|event| {
+                            project_ref.is_none() || event.project_ref.as_deref() == project_ref
+                        })
+                        .collect()
+                });
+                EmployeeScan {
+                    employee_reference,
+                    result,
+                }
+            })
+        })
+        .collect();
+
+    futures::future::join_all(scan_futures).await
+}
+
+fn is_within_range(date: &str, range_start: NaiveDate, range_end: NaiveDate) -> bool {
+    NaiveDate::parse_from_str(date, "%Y-%m-%d")
+        .map(|d| d >= range_start && d < range_end)
+        .unwrap_or(false)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_scan_range_returns_exclusive_end() {
+        let (start, end) = parse_scan_range("2026-05-04", "2026-05-10").unwrap();
+        assert_eq!(start, NaiveDate::from_ymd_opt(2026, 5, 4).unwrap());
+        assert_eq!(end, NaiveDate::from_ymd_opt(2026, 5, 11).unwrap());
+    }
+
+    #[test]
+    fn parse_scan_range_rejects_end_before_start() {
+        let error = parse_scan_range("2026-05-10", "2026-05-04").unwrap_err();
+        assert!(error.contains("Enddatum"), "got: {error}");
+    }
+
+    #[test]
+    fn parse_scan_range_rejects_ranges_longer_than_a_year() {
+        assert!(parse_scan_range("2026-01-01", "2026-12-31").is_ok());
+        assert!(parse_scan_range("2026-01-01", "2027-01-02").is_err());
+    }
+
+    #[test]
+    fn is_within_range_treats_end_as_exclusive() {
+        let start = NaiveDate::from_ymd_opt(2026, 5, 4).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 5, 11).unwrap();
+        assert!(is_within_range("2026-05-04", start, end));
+        assert!(is_within_range("2026-05-10", start, end));
+        assert!(!is_within_range("2026-05-11", start, end));
+        assert!(!is_within_range("not-a-date", start, end));
+    }
+}
@@ -0,0 +1,463 @@
+use chrono::NaiveDate;
+use std::collections::{BTreeSet, HashSet};
+
+use super::caldav::{
+    fetch_calendar_event, fetch_calendar_events_in_range, update_assignment_core, AssignmentWrite,
+    CaldavSession,
+};
+use super::capacity::{day_capacity, format_minutes, parse_minutes, working_window};
+use super::commands::model_for_calendar;
+use super::events::{classify_event, map_absence_raw_events_for_week};
+use super::types::PendingEvent;
+use crate::integrations::holidays::days_off;
+use crate::integrations::local_store::{AbsenceCategoryRule, LocalStore, WorkingTimeModel};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) struct AssignmentSlot {
+    pub(super) uid: String,
+    // Dense 0..n-1 position within the day.
+    pub(super) order_index: u32,
+    pub(super) start_time: String,
+    pub(super) end_time: String,
+}
+
+/// Splits `window` (minutes since midnight) into equal, consecutive slots, one per
+/// assignment. Assignments with an order index come first, by index; the rest follow by
+/// UID, so the result does not depend on the input order. Slots are half-open: one
+/// slot's end is the next slot's start.
+pub(super) fn allocate_slots(
+    assignments: &[(String, Option<u32>)],
+    window: (u32, u32),
+) -> Vec<AssignmentSlot> {
+    let mut sorted: Vec<&(String, Option<u32>)> = assignments.iter().collect();
+    sorted.sort_by(|(a_uid, a_index), (b_uid, b_index)| {
+        (a_index.is_none(), a_index, a_uid).cmp(&(b_index.is_none(), b_index, b_uid))
+    });
+    let mut seen_uids = HashSet::new();
+    sorted.retain(|(uid, _)| seen_uids.insert(uid.as_str()));
+
+    let (start, end) = window;
+    let total = end.saturating_sub(start);
+    let count = sorted.len() as u32;
+    sorted
+        .into_iter()
+        .enumerate()
+        .map(|(i, (uid, _))| {
+            let i = i as u32;
+            AssignmentSlot {
+                uid: uid.clone(),
+                order_index: i,
+                start_time: format_minutes(start + total * i / count),
+                end_time: format_minutes(start + total * (i + 1) / count),
+            }
+        })
+        .collect()
+}
+
+/// Allocates the lkr-planner assignments of `date` and returns those whose times or order
+/// index differ from their slot. UIDs in `requested_order` are placed first, in that order,
+/// ahead of the previously ordered ones. Bare events and events without an href are left alone.
+pub(super) fn slot_changes<'a>(
+    events: &'a [PendingEvent],
+    date: &str,
+    window: (u32, u32),
+    requested_order: &[String],
+) -> Vec<(&'a PendingEvent, AssignmentSlot)> {
+    let mut seen_uids = HashSet::new();
+    let assignments: Vec<&PendingEvent> = events
+        .iter()
+        .filter(|e| e.date == date && e.project_ref.is_some() && !e.href.is_empty())
+        .filter(|e| seen_uids.insert(e.uid.as_str()))
+        .collect();
+    let requested_count = requested_order.len() as u32;
+    let keys: Vec<(String, Option<u32>)> = assignments
+        .iter()
+        .map(|e| {
+            let index = match requested_order.iter().position(|uid| *uid == e.uid) {
+                Some(position) => Some(position as u32),
+                None => e.order_index.map(|index| requested_count + index),
+            };
+            (e.uid.clone(), index)
+        })
+        .collect();
+
+    allocate_slots(&keys, window)
+        .into_iter()
+        .filter_map(|slot| {
+            let event = assignments.iter().find(|e| e.uid == slot.uid)?;
+            let unchanged = event.start_time.as_deref() == Some(slot.start_time.as_str())
+                && event.end_time.as_deref() == Some(slot.end_time.as_str())
+                && event.order_index == Some(slot.order_index);
+            (!unchanged).then_some((*event, slot))
+        })
+        .collect()
+}
+
+/// Whether `day` is a public holiday or closure day for the employee. Only cached
+/// holidays are considered, as in the week view.
+pub(super) fn is_day_off(store: &LocalStore, employee_reference: &str, day: NaiveDate) -> bool {
+    days_off(store, employee_reference, day, day).contains(&day.format("%Y-%m-%d").to_string())
+}
+
+/// The window assignments on `day` are spread over: the longest stretch of the working
+/// window left by that day's absences. Returns `None` on a day off (see [`is_day_off`])
+/// and if an absence blocks the whole day. Days outside the working-time model are
+/// deliberate extra shifts and get the model's regular window; a failed absence lookup
+/// does too.
+pub(super) async fn free_window(
+    session: &CaldavSession,
+    absence_url: Option<&str>,
+    day: NaiveDate,
+    day_off: bool,
+    category_rules: &[AbsenceCategoryRule],
+    model: &WorkingTimeModel,
+) -> Option<(u32, u32)> {
+    if day_off {
+        return None;
+    }
+    let default_window = working_window(model);
+    let Some(absence_url) = absence_url.filter(|_| model.works_on(day)) else {
+        return Some(default_window);
+    };
+
+    let raw_events = match fetch_calendar_events_in_range(
+        session,
+        absence_url,
+        day,
+        day + chrono::Duration::days(1),
+    )
+    .await
+    {
+        Ok(raw_events) => raw_events,
+        Err(e) => {
+            eprintln!("calendar: absence lookup skipped for '{absence_url}': {e}");
+            return Some(default_window);
+        }
+    };
+
+    let absences = map_absence_raw_events_for_week(raw_events, day, category_rules);
+    let capacity = day_capacity(day, &absences, model);
+    match (
+        capacity.free_start_time.as_deref().and_then(parse_minutes),
+        capacity.free_end_time.as_deref().and_then(parse_minutes),
+    ) {
+        (Some(start), Some(end)) => Some((start, end)),
+        _ => None,
+    }
+}
+
+/// Re-slots all lkr-planner assignments in `calendar_url` on `day` and rewrites the ones
+/// whose slot changed. `requested_order` moves the given UIDs to the front, in that order.
+/// Returns the number of rewritten events. Running it again on the same day converges,
+/// so a partial failure is repaired by the next write.
+pub(super) async fn redistribute_day(
+    session: &CaldavSession,
+    store: &LocalStore,
+    calendar_url: &str,
+    day: NaiveDate,
+    requested_order: &[String],
+) -> Result<usize, String> {
+    let model = model_for_calendar(store, calendar_url);
+    let employee = store.employee_for_primary_calendar(calendar_url);
+    let absence_url = employee
+        .and_then(|s| s.zep_absence_calendar.as_deref())
+        .filter(|u| !u.is_empty());
+    let day_off = employee.is_some_and(|s| is_day_off(store, &s.daylite_contact_reference, day));
+    // A day off or fully absent day keeps its assignments; they are only lined up in the
+    // normal window.
+    let window = free_window(
+        session,
+        absence_url,
+        day,
+        day_off,
+        &store.absence_settings.category_rules,
+        model,
+    )
+    .await
+    .unwrap_or_else(|| working_window(model));
+
+    let events: Vec<PendingEvent> =
+        fetch_calendar_events_in_range(session, calendar_url, day, day + chrono::Duration::days(1))
+            .await?
+            .into_iter()
+            .map(classify_event)
+            .collect();
+
+    let date = day.format("%Y-%m-%d").to_string();
+    if let Some(unknown) = requested_order.iter().find(|uid| {
+        !events
+            .iter()
+            .any(|e| e.uid == **uid && e.date == date && e.project_ref.is_some())
+    }) {
+        return Err(format!(
+            "Der Einsatz {unknown} ist an diesem Tag nicht eingeplant."
+        ));
+    }
+    let changes = slot_changes(&events, &date, window, requested_order);
+    let mut failures = 0;
+    for (event, slot) in &changes {
+        let write = AssignmentWrite {
+            date: date.clone(),
+            project_ref: event.project_ref.clone().unwrap_or_default(),
+            project_name: event.summary.clone(),
+            start_time: slot.start_time.clone(),
+            end_time: slot.end_time.clone(),
+            order_index: Some(slot.order_index),
+        };
+        if let Err(e) = update_assignment_core(session, &event.href, &event.uid, &write).await {
+            eprintln!("calendar: re-slotting '{}' failed: {e}", event.href);
+            failures += 1;
+        }
+    }
+
+    if failures > 0 {
+        return Err(format!(
+            "{failures} Einsätze am {date} konnten nicht neu eingeteilt werden."
+        ));
+    }
+    Ok(changes.len())
+}
+
+pub(super) struct AssignmentPlacement {
+    pub(super) day: NaiveDate,
+    pub(super) order_index: Option<u32>,
+}
+
+/// Where an existing assignment currently sits, read before it is moved or deleted so its
+/// day can be re-slotted afterwards. Lookup failures only skip re-slotting.
+pub(super) async fn assignment_placement(
+    session: &CaldavSession,
+    href: &str,
+) -> Option<AssignmentPlacement> {
+    match fetch_calendar_event(session, href).await {
+        Ok(event) => event.and_then(|e| {
+            Some(AssignmentPlacement {
+                day: NaiveDate::parse_from_str(&e.dtstart, "%Y-%m-%d").ok()?,
+                order_index: e.order_index,
+            })
+        }),
+        Err(e) => {
+            eprintln!("calendar: could not read '{href}' before writing: {e}");
+            None
+        }
+    }
+}
+
+/// Runs [`redistribute_day`] after a write that already succeeded. A failure is logged
+/// rather than returned so the caller does not report the write itself as failed.
+pub(super) async fn redistribute_day_after_write(
+    session: &CaldavSession,
+    store: &LocalStore,
+    calendar_url: &str,
+    day: NaiveDate,
+) {
+    match redistribute_day(session, store, calendar_url, day, &[]).await {
+        Ok(0) => {}
+        Ok(rewritten) => {
+            eprintln!("calendar: re-slotted {rewritten} assignments in '{calendar_url}' on {day}")
+        }
+        Err(e) => eprintln!("calendar: re-slotting '{calendar_url}' on {day} failed: {e}"),
+    }
+}
+
+/// Re-slots every `(calendar_url, day)` touched by a bulk write, once per day.
+pub(super) async fn redistribute_days_after_write(
+    session: &CaldavSession,
+    store: &LocalStore,
+    days: BTreeSet<(String, NaiveDate)>,
+) {
+    for (calendar_url, day) in days {
+        redistribute_day_after_write(session, store, &calendar_url, day).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::local_store::{ClosureDay, EmployeeSetting};
+
+    const WINDOW: (u32, u32) = (480, 960);
+
+    fn unordered(uids: &[&str]) -> Vec<(String, Option<u32>)> {
+        uids.iter().map(|uid| (uid.to_string(), None)).collect()
+    }
+
+    fn times(slots: &[AssignmentSlot]) -> Vec<(&str, &str)> {
+        slots
+            .iter()
+            .map(|s| (s.start_time.as_str(), s.end_time.as_str()))
+            .collect()
+    }
+
+    fn assignment(uid: &str, start: Option<&str>, end: Option<&str>) -> PendingEvent {
+        PendingEvent {
+            uid: uid.to_string(),
+            date: "2026-05-04".to_string(),
+            summary: "Projekt".to_string(),
+            project_ref: Some("/v1/projects/1".to_string()),
+            start_time: start.map(str::to_string),
+            end_time: end.map(str::to_string),
+            href: format!("/calendars/{uid}.ics"),
+            last_modified: None,
+            order_index: None,
+        }
+    }
+
+    fn ordered(uid: &str, start: &str, end: &str, order_index: u32) -> PendingEvent {
+        PendingEvent {
+            order_index: Some(order_index),
+            ..assignment(uid, Some(start), Some(end))
+        }
+    }
+
+    #[test]
+    fn closure_days_are_days_off_for_their_group_only() {
+        let store = LocalStore {
+            employee_settings: vec![EmployeeSetting {
+                daylite_contact_reference: "/v1/contacts/1".to_string(),
+                employee_group: Some("Büro".to_string()),
+                ..EmployeeSetting::default()
+            }],
+            closure_days: vec![ClosureDay {
+                name: "Inventur".to_string(),
+                start_date: "2026-05-04".to_string(),
+                end_date: None,
+                employee_groups: vec!["Montage".to_string()],
+            }],
+            ..LocalStore::default()
+        };
+        let day = NaiveDate::from_ymd_opt(2026, 5, 4).unwrap();
+
+        assert!(!is_day_off(&store, "/v1/contacts/1", day));
+        assert!(!is_day_off(
+            &store,
+            "/v1/contacts/2",
+            day + chrono::Duration::days(1)
+        ));
+        assert!(!is_day_off(&store, "/v1/contacts/2", day));
+
+        let store = LocalStore {
+            closure_days: vec![ClosureDay {
+                employee_groups: vec![],
+                ..store.closure_days[0].clone()
+            }],
+            ..store
+        };
+        assert!(is_day_off(&store, "/v1/contacts/1", day));
+        assert!(is_day_off(&store, "/v1/contacts/2", day));
+    }
+
+    #[test]
+    fn allocate_slots_splits_the_window_evenly() {
+        assert!(allocate_slots(&[], WINDOW).is_empty());
+        assert_eq!(
+            times(&allocate_slots(&unordered(&["a"]), WINDOW)),
+            vec![("08:00", "16:00")]
+        );
+        assert_eq!(
+            times(&allocate_slots(&unordered(&["a", "b"]), WINDOW)),
+            vec![("08:00", "12:00"), ("12:00", "16:00")]
+        );
+        assert_eq!(
+            times(&allocate_slots(&unordered(&["a", "b", "c"]), WINDOW)),
+            vec![("08:00", "10:40"), ("10:40", "13:20"), ("13:20", "16:00")]
+        );
+    }
+
+    #[test]
+    fn allocate_slots_is_independent_of_input_order() {
+        let forward = allocate_slots(&unordered(&["a", "b", "c"]), WINDOW);
+        let shuffled = allocate_slots(&unordered(&["c", "a", "b"]), WINDOW);
+
+        assert_eq!(forward, shuffled);
+        assert_eq!(forward[0].uid, "a");
+    }
+
+    #[test]
+    fn allocate_slots_keeps_remainder_minutes_inside_the_window() {
+        let slots = allocate_slots(&unordered(&["a", "b", "c"]), (480, 961));
+
+        assert_eq!(slots[0].start_time, "08:00");
+        assert_eq!(slots[2].end_time, "16:01");
+    }
+
+    #[test]
+    fn slot_changes_skips_events_already_in_their_slot() {
+        let events = vec![
+            ordered("a", "08:00", "12:00", 0),
+            assignment("b", Some("08:00"), Some("16:00")),
+        ];
+
+        let changes = slot_changes(&events, "2026-05-04", WINDOW, &[]);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].0.uid, "b");
+        assert_eq!(changes[0].1.start_time, "12:00");
+        assert_eq!(changes[0].1.end_time, "16:00");
+    }
+
+    #[test]
+    fn slot_changes_never_touches_bare_events_or_other_days() {
+        let mut bare = assignment("bare", Some("09:00"), Some("10:00"));
+        bare.project_ref = None;
+        let mut other_day = assignment("other", Some("09:00"), Some("10:00"));
+        other_day.date = "2026-05-05".to_string();
+        let events = vec![bare, other_day, ordered("a", "08:00", "16:00", 0)];
+
+        assert!(slot_changes(&events, "2026-05-04", WINDOW, &[]).is_empty());
+    }
+
+    #[test]
+    fn slot_changes_gives_all_day_assignments_a_time_window() {
+        let events = vec![assignment("a", None, None)];
+
+        let changes = slot_changes(&events, "2026-05-04", WINDOW, &[]);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].1.start_time, "08:00");
+    }
+
+    #[test]
+    fn allocate_slots_puts_ordered_assignments_first() {
+        let slots = allocate_slots(
+            &[
+                ("a".to_string(), None),
+                ("z".to_string(), Some(1)),
+                ("m".to_string(), Some(0)),
+            ],
+            WINDOW,
+        );
+
+        let order: Vec<(&str, u32)> = slots
+            .iter()
+            .map(|s| (s.uid.as_str(), s.order_index))
+            .collect();
+        assert_eq!(order, vec![("m", 0), ("z", 1), ("a", 2)]);
+    }
+
+    #[test]
+    fn slot_changes_applies_the_requested_order() {
+        let events = vec![
+            ordered("a", "08:00", "12:00", 0),
+            ordered("b", "12:00", "16:00", 1),
+        ];
+
+        let changes = slot_changes(&events, "2026-05-04", WINDOW, &["b".to_string()]);
+
+        let moved: Vec<(&str, &str, u32)> = changes
+            .iter()
+            .map(|(e, s)| (e.uid.as_str(), s.start_time.as_str(), s.order_index))
+            .collect();
+        assert_eq!(moved, vec![("b", "08:00", 0), ("a", "12:00", 1)]);
+    }
+
+    #[test]
+    fn slot_changes_assigns_an_index_to_unordered_assignments() {
+        let events = vec![assignment("a", Some("08:00"), Some("16:00"))];
+
+        let changes = slot_changes(&events, "2026-05-04", WINDOW, &[]);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].1.order_index, 0);
+    }
+}
@@ -0,0 +1,444 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use super::commands::load_caldav_session;
+use super::scan::{parse_scan_range, scan_primary_calendars, EmployeeScan};
+use super::types::EmployeeScanError;
+use crate::integrations::daylite::projects::{
+    fetch_project_records, project_status_to_string, PlanningProjectRecord,
+};
+use crate::integrations::local_store::LocalStore;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStaffingReport {
+    pub projects: Vec<ProjectStaffingEntry>,
+    // Employees whose calendar could not be read; their assignments are missing from the totals.
+    pub employee_errors: Vec<EmployeeScanError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStaffingEntry {
+    pub project_ref: String,
+    pub project_name: String,
+    // None if the project no longer exists in Daylite or could not be looked up.
+    pub project_status: Option<String>,
+    pub project_due: Option<String>,
+    // One person-day per employee and date, however many events that day carries.
+    pub planned_person_days: u32,
+    // The employee's contracted daily hours (weekly hours / working days), split evenly
+    // between the projects that employee is planned on that day.
+    pub planned_hours: f64,
+    pub employee_references: Vec<String>,
+    pub first_planned_date: String,
+    pub last_planned_date: String,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_project_staffing_report(
+    app: tauri::AppHandle,
+    range_start: String,
+    range_end: String,
+) -> Result<ProjectStaffingReport, String> {
+    let (start, end) = parse_scan_range(&range_start, &range_end)?;
+    let store = crate::integrations::local_store::load_local_store(app.clone())
+        .map_err(|e| e.user_message)?;
+    let session = load_caldav_session(&store)?;
+
+    let scans = scan_primary_calendars(&store, &session, start, end, None).await;
+    let (aggregates, employee_errors) = aggregate_staffing(scans);
+
+    let project_refs: Vec<String> = aggregates.keys().cloned().collect();
+    let records = match fetch_project_records(app, &project_refs).await {
+        Ok(records) => Some(records),
+        Err(e) => {
+            eprintln!(
+                "staffing: Daylite lookup failed, falling back to cache: {}",
+                e.technical_message
+            );
+            None
+        }
+    };
+
+    Ok(ProjectStaffingReport {
+        projects: build_staffing_entries(aggregates, records.as_ref(), &store),
+        employee_errors,
+    })
+}
+
+#[derive(Debug, Default)]
+struct ProjectAggregate {
+    // (date, employee reference) -> share of that employee's day spent on this project. Keying
+    // by day makes person-days count each employee once per day.
+    employee_days: BTreeMap<(String, String), f64>,
+    summary: String,
+}
+
+fn aggregate_staffing(
+    scans: Vec<EmployeeScan>,
+) -> (BTreeMap<String, ProjectAggregate>, Vec<EmployeeScanError>) {
+    let mut aggregates: BTreeMap<String, ProjectAggregate> = BTreeMap::new();
+    let mut projects_per_day: HashMap<(String, String), BTreeSet<String>> = HashMap::new();
+    let mut errors = Vec::new();
+
+    for scan in scans {
+        let events = match scan.result {
+            Ok(events) => events,
+            Err(error) => {
+                errors.push(EmployeeScanError {
+                    employee_reference: scan.employee_reference,
+                    error,
+                });
+                continue;
+            }
+        };
+
+        for event in events {
+            let Some(project_ref) = event.project_ref else {
+                continue;
+            };
+            let day = (event.date, scan.employee_reference.clone());
+            projects_per_day
+                .entry(day.clone())
+                .or_default()
+                .insert(project_ref.clone());
+            let aggregate = aggregates.entry(project_ref).or_default();
+            aggregate.employee_days.insert(day, 1.0);
+            if aggregate.summary.is_empty() {
+                aggregate.summary = event.summary;
+            }
+        }
+    }
+
+    for aggregate in aggregates.values_mut() {
+        for (day, share) in aggregate.employee_days.iter_mut() {
+            *share = 1.0 / projects_per_day[day].len() as f64;
+        }
+    }
+
+    (aggregates, errors)
+}
+
+fn build_staffing_entries(
+    aggregates: BTreeMap<String, ProjectAggregate>,
+    records: Option<&HashMap<String, Option<PlanningProjectRecord>>>,
+    store: &LocalStore,
+) -> Vec<ProjectStaffingEntry> {
+    let mut entries: Vec<ProjectStaffingEntry> = aggregates
+        .into_iter()
+        .filter_map(|(project_ref, aggregate)| {
+            let ((first_planned_date, _), _) = aggregate.employee_days.first_key_value()?;
+            let ((last_planned_date, _), _) = aggregate.employee_days.last_key_value()?;
+            let (first_planned_date, last_planned_date) =
+                (first_planned_date.clone(), last_planned_date.clone());
+            let employee_references: Vec<String> = aggregate
+                .employee_days
+                .keys()
+                .map(|(_, employee)| employee.clone())
+                .collect::<BTreeSet<_>>()
+                .into_iter()
+                .collect();
+
+            let planned_hours: f64 = aggregate
+                .employee_days
+                .iter()
+                .map(|((_, employee), share)| {
+                    store.working_time_model_for(employee).daily_hours() * share
+                })
+                .sum();
+
+            let cached = store
+                .daylite_cache
+                .projects
+                .iter()
+                .find(|p| p.reference == project_ref);
+            let (project_name, project_status, project_due) =
+                match records.map(|r| r.get(&project_ref)) {
+                    Some(Some(Some(record))) => (
+                        record.name.clone(),
+                        Some(project_status_to_string(&record.status).to_string()),
+                        record.due.clone(),
+                    ),
+                    // Deleted in Daylite: the cache would report a status for a project that is gone.
+                    Some(Some(None)) => (aggregate.summary.clone(), None, None),
+                    _ => match cached {
+                        Some(entry) => (
+                            entry.name.clone(),
+                            Some(entry.status.clone()),
+                            entry.due.clone(),
+                        ),
+                        None => (aggregate.summary.clone(), None, None),
+                    },
+                };
+
+            Some(ProjectStaffingEntry {
+                project_ref,
+                project_name,
+                project_status,
+                project_due,
+                planned_person_days: aggregate.employee_days.len() as u32,
+                planned_hours: (planned_hours * 10.0).round() / 10.0,
+                employee_references,
+                first_planned_date,
+                last_planned_date,
+            })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| {
+        a.first_planned_date
+            .cmp(&b.first_planned_date)
+            .then_with(|| a.project_name.cmp(&b.project_name))
+            .then_with(|| a.project_ref.cmp(&b.project_ref))
+    });
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::calendar::types::PendingEvent;
+    use crate::integrations::daylite::projects::PlanningProjectStatus;
+    use crate::integrations::local_store::{
+        DayliteCache, DayliteProjectCacheEntry, EmployeeSetting, WorkingDay, WorkingTimeModel,
+    };
+
+    fn assignment(date: &str, project_ref: &str, summary: &str) -> PendingEvent {
+        PendingEvent {
+            uid: format!("{date}-{project_ref}"),
+            date: date.to_string(),
+            summary: summary.to_string(),
+            project_ref: Some(project_ref.to_string()),
+            start_time: Some("08:00".to_string()),
+            end_time: Some("16:00".to_string()),
+            href: String::new(),
+            last_modified: None,
+            order_index: None,
+        }
+    }
+
+    fn scan(employee: &str, result: Result<Vec<PendingEvent>, String>) -> EmployeeScan {
+        EmployeeScan {
+            employee_reference: employee.to_string(),
+            result,
+        }
+    }
+
+    fn record(reference: &str, name: &str, status: PlanningProjectStatus) -> PlanningProjectRecord {
+        PlanningProjectRecord {
+            reference: reference.to_string(),
+            name: name.to_string(),
+            status,
+            category: None,
+            keywords: vec![],
+            due: Some("2026-06-30T00:00:00.000Z".to_string()),
+            started: None,
+            completed: None,
+            create_date: None,
+            modify_date: None,
+        }
+    }
+
+    #[test]
+    fn counts_one_person_day_per_employee_and_date() {
+        let scans = vec![
+            scan(
+                "/v1/contacts/1",
+                Ok(vec![
+                    assignment("2026-05-04", "/v1/projects/1", "Nord"),
+                    // A second event for the same project on the same day is not extra capacity.
+                    assignment("2026-05-04", "/v1/projects/1", "Nord"),
+                    assignment("2026-05-05", "/v1/projects/1", "Nord"),
+                ]),
+            ),
+            scan(
+                "/v1/contacts/2",
+                Ok(vec![assignment("2026-05-04", "/v1/projects/1", "Nord")]),
+            ),
+        ];
+
+        let (aggregates, errors) = aggregate_staffing(scans);
+        let entries = build_staffing_entries(aggregates, None, &LocalStore::default());
+
+        assert!(errors.is_empty());
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].planned_person_days, 3);
+        assert_eq!(
+            entries[0].employee_references,
+            vec!["/v1/contacts/1".to_string(), "/v1/contacts/2".to_string()]
+        );
+        assert_eq!(entries[0].first_planned_date, "2026-05-04");
+        assert_eq!(entries[0].last_planned_date, "2026-05-05");
+    }
+
+    #[test]
+    fn planned_hours_follow_each_employees_working_time_model() {
+        let scans = vec![
+            scan(
+                "/v1/contacts/1",
+                Ok(vec![
+                    assignment("2026-05-04", "/v1/projects/1", "Nord"),
+                    assignment("2026-05-05", "/v1/projects/1", "Nord"),
+                ]),
+            ),
+            scan(
+                "/v1/contacts/2",
+                Ok(vec![assignment("2026-05-04", "/v1/projects/1", "Nord")]),
+            ),
+        ];
+        let store = LocalStore {
+            employee_settings: vec![EmployeeSetting {
+                daylite_contact_reference: "/v1/contacts/2".to_string(),
+                working_time_model: Some(WorkingTimeModel {
+                    working_days: vec![WorkingDay::Monday, WorkingDay::Tuesday, WorkingDay::Friday],
+                    start_time: "07:00".to_string(),
+                    end_time: "13:00".to_string(),
+                    weekly_hours: 20,
+                }),
+                ..EmployeeSetting::default()
+            }],
+            ..LocalStore::default()
+        };
+
+        let (aggregates, _) = aggregate_staffing(scans);
+        let entries = build_staffing_entries(aggregates, None, &store);
+
+        // Two default days of 8 h plus one part-time day of 20 h / 3 days.
+        assert_eq!(entries[0].planned_person_days, 3);
+        assert_eq!(entries[0].planned_hours, 22.7);
+    }
+
+    #[test]
+    fn planned_hours_split_a_day_between_its_projects() {
+        let scans = vec![scan(
+            "/v1/contacts/1",
+            Ok(vec![
+                assignment("2026-05-04", "/v1/projects/1", "Nord"),
+                assignment("2026-05-04", "/v1/projects/2", "Süd"),
+                assignment("2026-05-05", "/v1/projects/1", "Nord"),
+            ]),
+        )];
+
+        let (aggregates, _) = aggregate_staffing(scans);
+        let entries = build_staffing_entries(aggregates, None, &LocalStore::default());
+
+        // Half of the shared 8 h day plus the full day after it.
+        assert_eq!(entries[0].project_ref, "/v1/projects/1");
+        assert_eq!(entries[0].planned_person_days, 2);
+        assert_eq!(entries[0].planned_hours, 12.0);
+        assert_eq!(entries[1].project_ref, "/v1/projects/2");
+        assert_eq!(entries[1].planned_person_days, 1);
+        assert_eq!(entries[1].planned_hours, 4.0);
+    }
+
+    #[test]
+    fn ignores_bare_events_and_reports_failed_calendars() {
+        let mut bare = assignment("2026-05-04", "/v1/projects/1", "Werkstatt");
+        bare.project_ref = None;
+        let scans = vec![
+            scan("/v1/contacts/1", Ok(vec![bare])),
+            scan("/v1/contacts/2", Err("HTTP 500".to_string())),
+        ];
+
+        let (aggregates, errors) = aggregate_staffing(scans);
+
+        assert!(aggregates.is_empty());
+        assert_eq!(
+            errors,
+            vec![EmployeeScanError {
+                employee_reference: "/v1/contacts/2".to_string(),
+                error: "HTTP 500".to_string(),
+            }]
+        );
+    }
+
+    #[test]
+    fn uses_daylite_record_for_name_status_and_due() {
+        let (aggregates, _) = aggregate_staffing(vec![scan(
+            "/v1/contacts/1",
+            Ok(vec![assignment(
+                "2026-05-04",
+                "/v1/projects/1",
+                "Alter Name",
+            )]),
+        )]);
+        let mut records = HashMap::new();
+        records.insert(
+            "/v1/projects/1".to_string(),
+            Some(record(
+                "/v1/projects/1",
+                "Neuer Name",
+                PlanningProjectStatus::InProgress,
+            )),
+        );
+
+        let entries = build_staffing_entries(aggregates, Some(&records), &LocalStore::default());
+
+        assert_eq!(entries[0].project_name, "Neuer Name");
+        assert_eq!(entries[0].project_status, Some("in_progress".to_string()));
+        assert_eq!(
+            entries[0].project_due,
+            Some("2026-06-30T00:00:00.000Z".to_string())
+        );
+    }
+
+    #[test]
+    fn falls_back_to_cache_when_daylite_lookup_failed() {
+        let (aggregates, _) = aggregate_staffing(vec![scan(
+            "/v1/contacts/1",
+            Ok(vec![assignment("2026-05-04", "/v1/projects/1", "Summary")]),
+        )]);
+        let store = LocalStore {
+            daylite_cache: DayliteCache {
+                projects: vec![DayliteProjectCacheEntry {
+                    reference: "/v1/projects/1".to_string(),
+                    name: "Projekt Nord".to_string(),
+                    status: "new_status".to_string(),
+                    ..DayliteProjectCacheEntry::default()
+                }],
+                ..DayliteCache::default()
+            },
+            ..LocalStore::default()
+        };
+
+        let entries = build_staffing_entries(aggregates, None, &store);
+
+        assert_eq!(entries[0].project_name, "Projekt Nord");
+        assert_eq!(entries[0].project_status, Some("new_status".to_string()));
+        assert_eq!(entries[0].project_due, None);
+    }
+
+    #[test]
+    fn deleted_project_keeps_summary_and_has_no_status() {
+        let (aggregates, _) = aggregate_staffing(vec![scan(
+            "/v1/contacts/1",
+            Ok(vec![assignment("2026-05-04", "/v1/projects/1", "Summary")]),
+        )]);
+        let mut records = HashMap::new();
+        records.insert("/v1/projects/1".to_string(), None);
+
+        let entries = build_staffing_entries(aggregates, Some(&records), &LocalStore::default());
+
+        assert_eq!(entries[0].project_name, "Summary");
+        assert_eq!(entries[0].project_status, None);
+    }
+
+    #[test]
+    fn entries_are_sorted_by_first_planned_date() {
+        let (aggregates, _) = aggregate_staffing(vec![scan(
+            "/v1/contacts/1",
+            Ok(vec![
+                assignment("2026-05-06", "/v1/projects/1", "Später"),
+                assignment("2026-05-04", "/v1/projects/2", "Früher"),
+            ]),
+        )]);
+
+        let entries = build_staffing_entries(aggregates, None, &LocalStore::default());
+
+        assert_eq!(entries[0].project_ref, "/v1/projects/2");
+        assert_eq!(entries[1].project_ref, "/v1/projects/1");
+    }
+}
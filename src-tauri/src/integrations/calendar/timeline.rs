@@ -0,0 +1,159 @@
+use serde::{Deserialize, Serialize};
+use specta::Type;
+
+use super::commands::load_caldav_session;
+use super::scan::{parse_scan_range, scan_primary_calendars, EmployeeScan};
+use super::types::EmployeeScanError;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTimeline {
+    pub project_ref: String,
+    pub entries: Vec<ProjectTimelineEntry>,
+    // Employees whose calendar could not be read; the timeline may be missing their assignments.
+    pub employee_errors: Vec<EmployeeScanError>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectTimelineEntry {
+    pub employee_reference: String,
+    pub uid: String,
+    pub date: String,
+    pub title: String,
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub href: Option<String>,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn get_project_timeline(
+    app: tauri::AppHandle,
+    project_ref: String,
+    range_start: String,
+    range_end: String,
+) -> Result<ProjectTimeline, String> {
+    let project_ref = project_ref.trim().to_string();
+    if project_ref.is_empty() {
+        return Err("Projektreferenz fehlt.".to_string());
+    }
+    let (start, end) = parse_scan_range(&range_start, &range_end)?;
+    let store =
+        crate::integrations::local_store::load_local_store(app).map_err(|e| e.user_message)?;
+    let session = load_caldav_session(&store)?;
+
+    let scans = scan_primary_calendars(&store, &session, start, end, Some(&project_ref)).await;
+    let (entries, employee_errors) = build_timeline_entries(scans);
+
+    Ok(ProjectTimeline {
+        project_ref,
+        entries,
+        employee_errors,
+    })
+}
+
+fn build_timeline_entries(
+    scans: Vec<EmployeeScan>,
+) -> (Vec<ProjectTimelineEntry>, Vec<EmployeeScanError>) {
+    let mut entries = Vec::new();
+    let mut errors = Vec::new();
+
+    for scan in scans {
+        match scan.result {
+            Ok(events) => {
+                entries.extend(events.into_iter().map(|event| ProjectTimelineEntry {
+                    employee_reference: scan.employee_reference.clone(),
+                    uid: event.uid,
+                    date: event.date,
+                    title: event.summary,
+                    start_time: event.start_time,
+                    end_time: event.end_time,
+                    href: Some(event.href).filter(|h| !h.is_empty()),
+                }));
+            }
+            Err(error) => errors.push(EmployeeScanError {
+                employee_reference: scan.employee_reference,
+                error,
+            }),
+        }
+    }
+
+    entries.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| a.start_time.cmp(&b.start_time))
+            .then_with(|| a.employee_reference.cmp(&b.employee_reference))
+            .then_with(|| a.uid.cmp(&b.uid))
+    });
+    (entries, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::calendar::types::PendingEvent;
+
+    fn assignment(uid: &str, date: &str, start_time: &str) -> PendingEvent {
+        PendingEvent {
+            uid: uid.to_string(),
+            date: date.to_string(),
+            summary: "Projekt Nord".to_string(),
+            project_ref: Some("/v1/projects/1".to_string()),
+            start_time: Some(start_time.to_string()),
+            end_time: Some("16:00".to_string()),
+            href: format!("/calendars/{uid}.ics"),
+            last_modified: None,
+            order_index: None,
+        }
+    }
+
+    fn scan(employee: &str, result: Result<Vec<PendingEvent>, String>) -> EmployeeScan {
+        EmployeeScan {
+            employee_reference: employee.to_string(),
+            result,
+        }
+    }
+
+    #[test]
+    fn entries_are_sorted_chronologically_across_employees() {
+        let scans = vec![
+            scan(
+                "/v1/contacts/2",
+                Ok(vec![
+                    assignment("b-1", "2026-05-06", "08:00"),
+                    assignment("b-2", "2026-05-04", "12:00"),
+                ]),
+            ),
+            scan(
+                "/v1/contacts/1",
+                Ok(vec![assignment("a-1", "2026-05-04", "08:00")]),
+            ),
+        ];
+
+        let (entries, errors) = build_timeline_entries(scans);
+
+        assert!(errors.is_empty());
+        let uids: Vec<&str> = entries.iter().map(|e| e.uid.as_str()).collect();
+        assert_eq!(uids, vec!["a-1", "b-2", "b-1"]);
+        assert_eq!(entries[0].employee_reference, "/v1/contacts/1");
+        assert_eq!(entries[0].href, Some("/calendars/a-1.ics".to_string()));
+    }
+
+    #[test]
+    fn failed_calendars_are_reported_separately() {
+        let scans = vec![
+            scan(
+                "/v1/contacts/1",
+                Ok(vec![assignment("a-1", "2026-05-04", "08:00")]),
+            ),
+            scan("/v1/contacts/2", Err("HTTP 500".to_string())),
+        ];
+
+        let (entries, errors) = build_timeline_entries(scans);
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].employee_reference, "/v1/contacts/2");
+    }
+}
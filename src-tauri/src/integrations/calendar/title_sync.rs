@@ -0,0 +1,301 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeSet, HashMap};
+
+use super::caldav::{update_assignment_core, AssignmentWrite};
+use super::capacity::working_window_times;
+use super::commands::load_caldav_session;
+use super::scan::{parse_scan_range, scan_primary_calendars, EmployeeScan};
+use super::types::{AssignmentWriteFailure, EmployeeScanError};
+use crate::integrations::daylite::projects::{fetch_project_records, PlanningProjectRecord};
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentTitleChange {
+    pub employee_reference: String,
+    pub uid: String,
+    pub href: String,
+    pub date: String,
+    pub project_ref: String,
+    pub current_title: String,
+    pub new_title: String,
+    // Kept so the rewrite neither moves nor reorders the assignment.
+    pub start_time: Option<String>,
+    pub end_time: Option<String>,
+    pub order_index: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct TitleReconciliationReport {
+    // Every out-of-date assignment, whether or not it was rewritten.
+    pub changes: Vec<AssignmentTitleChange>,
+    pub dry_run: bool,
+    // Only filled when dry_run is false.
+    pub write_failures: Vec<AssignmentWriteFailure>,
+    pub employee_errors: Vec<EmployeeScanError>,
+}
+
+/// Rewrites assignment titles that still carry an old Daylite project name.
+/// With `dry_run` the intended changes are listed but nothing is written.
+#[tauri::command]
+#[specta::specta]
+pub async fn reconcile_assignment_titles(
+    app: tauri::AppHandle,
+    range_start: String,
+    range_end: String,
+    dry_run: bool,
+) -> Result<TitleReconciliationReport, String> {
+    let (start, end) = parse_scan_range(&range_start, &range_end)?;
+    let store = crate::integrations::local_store::load_local_store(app.clone())
+        .map_err(|e| e.user_message)?;
+    let session = load_caldav_session(&store)?;
+
+    let scans = scan_primary_calendars(&store, &session, start, end, None).await;
+    let project_refs: Vec<String> = scans
+        .iter()
+        .filter_map(|scan| scan.result.as_ref().ok())
+        .flatten()
+        .filter_map(|event| event.project_ref.clone())
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    let records = fetch_project_records(app, &project_refs)
+        .await
+        .map_err(|e| e.user_message)?;
+
+    let (changes, employee_errors) = collect_title_changes(scans, &records);
+
+    let mut write_failures = Vec::new();
+    if !dry_run {
+        for change in &changes {
+            let (default_start, default_end) =
+                working_window_times(store.working_time_model_for(&change.employee_reference));
+            let write = AssignmentWrite {
+                date: change.date.clone(),
+                project_ref: change.project_ref.clone(),
+                project_name: change.new_title.clone(),
+                start_time: change.start_time.clone().unwrap_or(default_start),
+                end_time: change.end_time.clone().unwrap_or(default_end),
+                order_index: change.order_index,
+            };
+            if let Err(error) =
+                update_assignment_core(&session, &change.href, &change.uid, &write).await
+            {
+                write_failures.push(AssignmentWriteFailure {
+                    href: change.href.clone(),
+                    error,
+                });
+            }
+        }
+        eprintln!(
+            "title_sync: rewrote {} of {} out-of-date assignment titles",
+            changes.len() - write_failures.len(),
+            changes.len()
+        );
+    }
+
+    Ok(TitleReconciliationReport {
+        changes,
+        dry_run,
+        write_failures,
+        employee_errors,
+    })
+}
+
+/// A title is out of date when it differs from the Daylite name and the project was
+/// modified after the event. A SUMMARY edited in the calendar after the last project
+/// change is treated as intentional. Missing timestamps fall back to the name comparison.
+fn is_title_outdated(
+    summary: &str,
+    event_modified: Option<DateTime<Utc>>,
+    record: &PlanningProjectRecord,
+) -> bool {
+    if summary == record.name || record.name.trim().is_empty() {
+        return false;
+    }
+    let project_modified = record
+        .modify_date
+        .as_deref()
+        .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+        .map(|dt| dt.with_timezone(&Utc));
+    match (project_modified, event_modified) {
+        (Some(project_modified), Some(event_modified)) => project_modified > event_modified,
+        _ => true,
+    }
+}
+
+fn collect_title_changes(
+    scans: Vec<EmployeeScan>,
+    records: &HashMap<String, Option<PlanningProjectRecord>>,
+) -> (Vec<AssignmentTitleChange>, Vec<EmployeeScanError>) {
+    let mut changes = Vec::new();
+    let mut errors = Vec::new();
+
+    for scan in scans {
+        let events = match scan.result {
+            Ok(events) => events,
+            Err(error) => {
+                errors.push(EmployeeScanError {
+                    employee_reference: scan.employee_reference,
+                    error,
+                });
+                continue;
+            }
+        };
+
+        for event in events {
+            let Some(project_ref) = event.project_ref else {
+                continue;
+            };
+            // Deleted projects are the orphan scan's business, not a rename.
+            let Some(Some(record)) = records.get(&project_ref) else {
+                continue;
+            };
+            if event.href.is_empty()
+                || !is_title_outdated(&event.summary, event.last_modified, record)
+            {
+                continue;
+            }
+            changes.push(AssignmentTitleChange {
+                employee_reference: scan.employee_reference.clone(),
+                uid: event.uid,
+                href: event.href,
+                date: event.date,
+                project_ref,
+                current_title: event.summary,
+                new_title: record.name.clone(),
+                start_time: event.start_time,
+                end_time: event.end_time,
+                order_index: event.order_index,
+            });
+        }
+    }
+
+    changes.sort_by(|a, b| {
+        a.date
+            .cmp(&b.date)
+            .then_with(|| a.employee_reference.cmp(&b.employee_reference))
+            .then_with(|| a.uid.cmp(&b.uid))
+    });
+    (changes, errors)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::calendar::types::PendingEvent;
+    use crate::integrations::daylite::projects::PlanningProjectStatus;
+
+    fn utc(raw: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(raw)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn record(name: &str, modify_date: Option<&str>) -> PlanningProjectRecord {
+        PlanningProjectRecord {
+            reference: "/v1/projects/1".to_string(),
+            name: name.to_string(),
+            status: PlanningProjectStatus::InProgress,
+            category: None,
+            keywords: vec![],
+            due: None,
+            started: None,
+            completed: None,
+            create_date: None,
+            modify_date: modify_date.map(str::to_string),
+        }
+    }
+
+    fn assignment(uid: &str, summary: &str, last_modified: Option<&str>) -> PendingEvent {
+        PendingEvent {
+            uid: uid.to_string(),
+            date: "2026-05-04".to_string(),
+            summary: summary.to_string(),
+            project_ref: Some("/v1/projects/1".to_string()),
+            start_time: Some("08:00".to_string()),
+            end_time: Some("16:00".to_string()),
+            href: format!("/calendars/{uid}.ics"),
+            last_modified: last_modified.map(utc),
+            order_index: None,
+        }
+    }
+
+    #[test]
+    fn title_is_outdated_when_project_changed_after_event() {
+        let record = record("Neu", Some("2026-03-01T00:00:00.000Z"));
+
+        assert!(is_title_outdated(
+            "Alt",
+            Some(utc("2026-02-01T00:00:00Z")),
+            &record
+        ));
+        assert!(!is_title_outdated(
+            "Alt",
+            Some(utc("2026-04-01T00:00:00Z")),
+            &record
+        ));
+        assert!(!is_title_outdated(
+            "Neu",
+            Some(utc("2026-02-01T00:00:00Z")),
+            &record
+        ));
+    }
+
+    #[test]
+    fn title_comparison_falls_back_to_name_without_timestamps() {
+        assert!(is_title_outdated("Alt", None, &record("Neu", None)));
+        assert!(is_title_outdated(
+            "Alt",
+            Some(utc("2026-04-01T00:00:00Z")),
+            &record("Neu", Some("not-a-date"))
+        ));
+        assert!(!is_title_outdated("Alt", None, &record("  ", None)));
+    }
+
+    #[test]
+    fn collect_title_changes_lists_stale_assignments_only() {
+        let scans = vec![
+            EmployeeScan {
+                employee_reference: "/v1/contacts/1".to_string(),
+                result: Ok(vec![
+                    assignment("a-1", "Alt", Some("2026-02-01T00:00:00Z")),
+                    assignment("a-2", "Neu", Some("2026-02-01T00:00:00Z")),
+                ]),
+            },
+            EmployeeScan {
+                employee_reference: "/v1/contacts/2".to_string(),
+                result: Err("HTTP 500".to_string()),
+            },
+        ];
+        let mut records = HashMap::new();
+        records.insert(
+            "/v1/projects/1".to_string(),
+            Some(record("Neu", Some("2026-03-01T00:00:00.000Z"))),
+        );
+
+        let (changes, errors) = collect_title_changes(scans, &records);
+
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].uid, "a-1");
+        assert_eq!(changes[0].current_title, "Alt");
+        assert_eq!(changes[0].new_title, "Neu");
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn collect_title_changes_skips_deleted_projects() {
+        let scans = vec![EmployeeScan {
+            employee_reference: "/v1/contacts/1".to_string(),
+            result: Ok(vec![assignment("a-1", "Alt", None)]),
+        }];
+        let mut records = HashMap::new();
+        records.insert("/v1/projects/1".to_string(), None);
+
+        let (changes, _) = collect_title_changes(scans, &records);
+
+        assert!(changes.is_empty());
+    }
+}
@@ -1,7 +1,9 @@
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use specta::Type;
 
+use crate::integrations::local_store::AbsenceCategory;
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub enum CalendarEventKind {
@@ -26,6 +28,12 @@ pub struct CalendarCellEvent {
     pub href: Option<String>,
     // Daylite project reference (e.g. "/v1/projects/42") stored in DESCRIPTION. None for bare events.
     pub project_ref: Option<String>,
+    // Set for absences only.
+    pub absence_category: Option<AbsenceCategory>,
+    // Length of a timed (partial-day) absence. None for all-day absences and non-absences.
+    pub absence_minutes: Option<u32>,
+    // Planner-defined position among the employee's assignments that day. None if never ordered.
+    pub order_index: Option<u32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
@@ -34,19 +42,42 @@ pub struct EmployeeWeekEvents {
     pub employee_reference: String,
     pub events: Vec<CalendarCellEvent>,
     pub error: Option<String>,
+    // One entry per day of the week. Empty when the calendar could not be read.
+    #[serde(default)]
+    pub day_capacities: Vec<DayCapacity>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DayCapacity {
+    pub date: String,
+    // False if the employee's working-time model excludes this weekday or it is a public
+    // holiday in the employee's subdivision.
+    pub is_working_day: bool,
+    // Working minutes left after absences.
+    pub available_minutes: u32,
+    // Longest remaining window; new assignments are placed here. None if the day is blocked.
+    pub free_start_time: Option<String>,
+    pub free_end_time: Option<String>,
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
-pub(super) struct RawVEvent {
+pub(crate) struct RawVEvent {
     pub(super) uid: String,
-    pub(super) summary: String,
+    pub(crate) summary: String,
     pub(super) description: String,
-    pub(super) dtstart: String,
+    pub(crate) dtstart: String,
     // Exclusive end date for all-day events (DATE values only).
-    pub(super) dtend: Option<NaiveDate>,
+    pub(crate) dtend: Option<NaiveDate>,
     pub(super) start_time: Option<String>,
     pub(super) end_time: Option<String>,
     pub(super) href: String,
+    // CATEGORIES values, split on commas across all CATEGORIES lines.
+    pub(super) categories: Vec<String>,
+    // LAST-MODIFIED, falling back to DTSTAMP; None if the server sent neither.
+    pub(super) last_modified: Option<DateTime<Utc>>,
+    // X-LKR-ORDER; None for events written before ordering existed and for foreign events.
+    pub(super) order_index: Option<u32>,
 }
 
 pub(super) struct PendingEvent {
@@ -58,4 +89,54 @@ pub(super) struct PendingEvent {
     pub(super) start_time: Option<String>,
     pub(super) end_time: Option<String>,
     pub(super) href: String,
+    pub(super) last_modified: Option<DateTime<Utc>>,
+    pub(super) order_index: Option<u32>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmployeeScanError {
+    pub employee_reference: String,
+    pub error: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentWriteFailure {
+    pub href: String,
+    pub error: String,
+}
+
+// Outcome of a bulk write: every href lands in exactly one of the two lists.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentBulkResult {
+    pub succeeded: Vec<String>,
+    pub failed: Vec<AssignmentWriteFailure>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum AssignmentConflictKind {
+    // Same employee, day and project more than once.
+    Duplicate,
+    // Two assignments of one employee whose time windows intersect.
+    Overlap,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AssignmentConflict {
+    pub kind: AssignmentConflictKind,
+    pub date: String,
+    pub uids: Vec<String>,
+    // Set for duplicates only; overlapping assignments usually belong to different projects.
+    pub project_ref: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct EmployeeAssignmentConflicts {
+    pub employee_reference: String,
+    pub conflicts: Vec<AssignmentConflict>,
 }
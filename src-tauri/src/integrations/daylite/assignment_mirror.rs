@@ -0,0 +1,410 @@
+use super::auth_flow::{send_authenticated_json, send_authenticated_request};
+use super::client::{DayliteApiClient, DayliteHttpMethod, DayliteHttpRequest};
+use super::shared::{
+    with_token_refresh_lock, DayliteApiError, DayliteSearchResult, DayliteTokenState,
+};
+use crate::integrations::local_store::LocalStore;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::{json, Value};
+
+// The note is found again by this exact title, so it must not be localised or reworded.
+const PLAN_NOTE_TITLE: &str = "Einsatzplanung (Planer)";
+const PLAN_NOTE_HEADER: &str =
+    "Automatisch vom Einsatzplaner gepflegt – Änderungen in dieser Notiz werden überschrieben.";
+
+/// Where an assignment is planned, as mirrored into Daylite.
+pub(crate) struct MirroredAssignment {
+    pub(crate) date: NaiveDate,
+    pub(crate) project_ref: String,
+    pub(crate) employee_reference: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct DaylitePlanNoteDto {
+    #[serde(rename = "self")]
+    reference: String,
+    #[serde(default)]
+    details: Option<String>,
+    #[serde(default)]
+    projects: Vec<DaylitePlanNoteProjectDto>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct DaylitePlanNoteProjectDto {
+    project: String,
+}
+
+impl DaylitePlanNoteDto {
+    fn links_project(&self, project_ref: &str) -> bool {
+        self.projects
+            .iter()
+            .any(|link| link.project.trim() == project_ref)
+    }
+}
+
+/// Writes an assignment's line into the plan note of its project, or removes it from every
+/// plan note when `assignment` is None. Lines are keyed by the assignment UID, so repeating
+/// a write changes nothing and a moved assignment leaves its old project's note.
+///
+/// Best effort and detached: the calendar write has already succeeded, so a slow or
+/// unreachable Daylite must not hold up the command, and failures are only logged.
+pub(crate) fn spawn_assignment_mirror(
+    store: &LocalStore,
+    uid: String,
+    assignment: Option<MirroredAssignment>,
+) {
+    if !store.assignment_mirror.enabled {
+        return;
+    }
+    let store = store.clone();
+    tauri::async_runtime::spawn(async move {
+        mirror_assignment(&store, &uid, assignment).await;
+    });
+}
+
+async fn mirror_assignment(store: &LocalStore, uid: &str, assignment: Option<MirroredAssignment>) {
+    let planned = assignment.map(|assignment| PlannedLine {
+        project_ref: assignment.project_ref.trim().to_string(),
+        text: plan_line(
+            assignment.date,
+            &employee_name(store, &assignment.employee_reference),
+            uid,
+        ),
+    });
+
+    let client = match DayliteApiClient::new(&store.api_endpoints.daylite_base_url) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!(
+                "daylite: assignment mirror skipped: {}",
+                e.technical_message
+            );
+            return;
+        }
+    };
+    let result = with_token_refresh_lock(|tokens| {
+        mirror_assignment_core(&client, tokens, uid, planned.as_ref())
+    })
+    .await;
+    if let Err(e) = result {
+        eprintln!(
+            "daylite: assignment mirror for {uid} failed: {}",
+            e.technical_message
+        );
+    }
+}
+
+struct PlannedLine {
+    project_ref: String,
+    text: String,
+}
+
+async fn mirror_assignment_core(
+    client: &DayliteApiClient,
+    token_state: DayliteTokenState,
+    uid: &str,
+    planned: Option<&PlannedLine>,
+) -> Result<((), DayliteTokenState), DayliteApiError> {
+    let (notes, mut token_state) = search_plan_notes(
+        client,
+        token_state,
+        json!({ "details": { "contains": uid_marker(uid) } }),
+    )
+    .await?;
+
+    let mut target_note = None;
+    for note in notes {
+        if target_note.is_none()
+            && planned.is_some_and(|planned| note.links_project(&planned.project_ref))
+        {
+            target_note = Some(note);
+            continue;
+        }
+        token_state = update_plan_note(client, token_state, &note, uid, None).await?;
+    }
+
+    let Some(planned) = planned else {
+        return Ok(((), token_state));
+    };
+    if target_note.is_none() {
+        let (notes, updated_tokens) = search_plan_notes(
+            client,
+            token_state,
+            json!({ "projects": { "any": { "project": { "equal": planned.project_ref } } } }),
+        )
+        .await?;
+        token_state = updated_tokens;
+        target_note = notes.into_iter().next();
+    }
+
+    let token_state = match target_note {
+        Some(note) => update_plan_note(client, token_state, &note, uid, Some(planned)).await?,
+        None => {
+            send_authenticated_request(
+                client,
+                token_state,
+                DayliteHttpRequest {
+                    body: Some(json!({
+                        "title": PLAN_NOTE_TITLE,
+                        "details": plan_details("", uid, Some(&planned.text)),
+                        "projects": [{ "project": planned.project_ref }],
+                    })),
+                    // A repeated create after a lost response would add a second plan note.
+                    retryable: false,
+                    ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/notes")
+                },
+            )
+            .await?
+        }
+    };
+    Ok(((), token_state))
+}
+
+/// Plan notes matching `filter`; the title condition is added here.
+async fn search_plan_notes(
+    client: &DayliteApiClient,
+    token_state: DayliteTokenState,
+    mut filter: Value,
+) -> Result<(Vec<DaylitePlanNoteDto>, DayliteTokenState), DayliteApiError> {
+    filter["title"] = json!({ "equal": PLAN_NOTE_TITLE });
+    let (result, token_state) = send_authenticated_json::<DayliteSearchResult<DaylitePlanNoteDto>>(
+        client,
+        token_state,
+        DayliteHttpRequest {
+            query: vec![("full-records".to_string(), "true".to_string())],
+            body: Some(filter),
+            ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/notes/_search")
+        },
+    )
+    .await?;
+    Ok((result.results, token_state))
+}
+
+/// Skips the PATCH when the note already reads as it should.
+async fn update_plan_note(
+    client: &DayliteApiClient,
+    token_state: DayliteTokenState,
+    note: &DaylitePlanNoteDto,
+    uid: &str,
+    planned: Option<&PlannedLine>,
+) -> Result<DayliteTokenState, DayliteApiError> {
+    let current = note.details.as_deref().unwrap_or_default();
+    let details = plan_details(current, uid, planned.map(|planned| planned.text.as_str()));
+    if details == current {
+        return Ok(token_state);
+    }
+
+    let reference = note.reference.trim();
+    let path = reference.strip_prefix("/v1").unwrap_or(reference);
+    send_authenticated_request(
+        client,
+        token_state,
+        DayliteHttpRequest {
+            body: Some(json!({ "details": details })),
+            ..DayliteHttpRequest::new(DayliteHttpMethod::Patch, path)
+        },
+    )
+    .await
+}
+
+fn uid_marker(uid: &str) -> String {
+    format!("[{uid}]")
+}
+
+fn plan_line(date: NaiveDate, employee: &str, uid: &str) -> String {
+    format!(
+        "{} – {employee} {}",
+        date.format("%d.%m.%Y"),
+        uid_marker(uid)
+    )
+}
+
+fn employee_name(store: &LocalStore, employee_reference: &str) -> String {
+    store
+        .daylite_cache
+        .contacts
+        .iter()
+        .find(|contact| contact.reference == employee_reference)
+        .and_then(|contact| contact.full_name.clone().or(contact.nickname.clone()))
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| employee_reference.to_string())
+}
+
+/// Rebuilds a plan note with `uid`'s line replaced by `line`, or dropped if None. Lines
+/// are sorted by date; text that is not a plan line is discarded, as the header warns.
+fn plan_details(current: &str, uid: &str, line: Option<&str>) -> String {
+    let marker = uid_marker(uid);
+    let mut lines: Vec<(Option<NaiveDate>, &str)> = current
+        .lines()
+        .map(str::trim)
+        .filter(|l| l.ends_with(']') && l.contains(" [") && !l.ends_with(&marker))
+        .chain(line)
+        .map(|l| {
+            let date = l
+                .get(..10)
+                .and_then(|raw| NaiveDate::parse_from_str(raw, "%d.%m.%Y").ok());
+            (date, l)
+        })
+        .collect();
+    lines.sort();
+
+    let mut details = PLAN_NOTE_HEADER.to_string();
+    details.push('\n');
+    if lines.is_empty() {
+        details.push_str("\nKeine Einsätze geplant.");
+    }
+    for (_, l) in lines {
+        details.push('\n');
+        details.push_str(l);
+    }
+    details
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::daylite::test_support::{
+        mock_response, valid_token_state, MockTransport,
+    };
+
+    fn planned(project_ref: &str, date: &str, employee: &str) -> PlannedLine {
+        PlannedLine {
+            project_ref: project_ref.to_string(),
+            text: plan_line(
+                NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap(),
+                employee,
+                "uid-1",
+            ),
+        }
+    }
+
+    #[test]
+    fn creates_plan_note_when_project_has_none() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(200, r#"{"results":[]}"#)),
+                Ok(mock_response(200, r#"{"results":[]}"#)),
+                Ok(mock_response(201, r#"{"self":"/v1/notes/9"}"#)),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let line = planned("/v1/projects/3001", "2026-03-02", "Max Muster");
+
+            mirror_assignment_core(&client, valid_token_state(), "uid-1", Some(&line))
+                .await
+                .expect("mirror should succeed");
+
+            let requests = transport.requests();
+            assert_eq!(requests.len(), 3);
+            assert_eq!(
+                requests[0].body,
+                Some(json!({
+                    "details": { "contains": "[uid-1]" },
+                    "title": { "equal": PLAN_NOTE_TITLE }
+                }))
+            );
+            assert_eq!(requests[2].path, "/notes");
+            assert!(!requests[2].retryable);
+            assert_eq!(
+                requests[2].body,
+                Some(json!({
+                    "title": PLAN_NOTE_TITLE,
+                    "details": format!("{PLAN_NOTE_HEADER}\n\n02.03.2026 – Max Muster [uid-1]"),
+                    "projects": [{ "project": "/v1/projects/3001" }],
+                }))
+            );
+        });
+    }
+
+    #[test]
+    fn moved_assignment_leaves_old_note_and_joins_new_one_in_date_order() {
+        tauri::async_runtime::block_on(async {
+            let old_note = json!({
+                "results": [{
+                    "self": "/v1/notes/1",
+                    "details": format!("{PLAN_NOTE_HEADER}\n\n02.03.2026 – Max Muster [uid-1]"),
+                    "projects": [{ "project": "/v1/projects/1" }]
+                }]
+            });
+            let new_note = json!({
+                "results": [{
+                    "self": "/v1/notes/2",
+                    "details": format!("{PLAN_NOTE_HEADER}\n\n05.03.2026 – Erika Muster [uid-2]"),
+                    "projects": [{ "project": "/v1/projects/2" }]
+                }]
+            });
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(200, &old_note.to_string())),
+                Ok(mock_response(204, "")),
+                Ok(mock_response(200, &new_note.to_string())),
+                Ok(mock_response(200, "{}")),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let line = planned("/v1/projects/2", "2026-03-04", "Max Muster");
+
+            mirror_assignment_core(&client, valid_token_state(), "uid-1", Some(&line))
+                .await
+                .expect("mirror should succeed");
+
+            let requests = transport.requests();
+            assert_eq!(requests[1].method, DayliteHttpMethod::Patch);
+            assert_eq!(requests[1].path, "/notes/1");
+            assert_eq!(
+                requests[1].body,
+                Some(json!({
+                    "details": format!("{PLAN_NOTE_HEADER}\n\nKeine Einsätze geplant.")
+                }))
+            );
+            assert_eq!(requests[3].path, "/notes/2");
+            assert_eq!(
+                requests[3].body,
+                Some(json!({
+                    "details": format!(
+                        "{PLAN_NOTE_HEADER}\n\n04.03.2026 – Max Muster [uid-1]\n05.03.2026 – Erika Muster [uid-2]"
+                    )
+                }))
+            );
+        });
+    }
+
+    #[test]
+    fn repeated_write_leaves_note_untouched() {
+        tauri::async_runtime::block_on(async {
+            let note = json!({
+                "results": [{
+                    "self": "/v1/notes/1",
+                    "details": format!("{PLAN_NOTE_HEADER}\n\n02.03.2026 – Max Muster [uid-1]"),
+                    "projects": [{ "project": "/v1/projects/1" }]
+                }]
+            });
+            let transport = MockTransport::new(vec![Ok(mock_response(200, &note.to_string()))]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let line = planned("/v1/projects/1", "2026-03-02", "Max Muster");
+
+            mirror_assignment_core(&client, valid_token_state(), "uid-1", Some(&line))
+                .await
+                .expect("mirror should succeed");
+
+            assert_eq!(transport.requests().len(), 1);
+        });
+    }
+
+    #[test]
+    fn employee_name_falls_back_to_reference() {
+        let store = LocalStore {
+            daylite_cache: crate::integrations::local_store::DayliteCache {
+                contacts: vec![crate::integrations::local_store::DayliteContactCacheEntry {
+                    reference: "/v1/contacts/7".to_string(),
+                    full_name: Some(" Max Muster ".to_string()),
+                    ..Default::default()
+                }],
+                ..Default::default()
+            },
+            ..LocalStore::default()
+        };
+
+        assert_eq!(employee_name(&store, "/v1/contacts/7"), "Max Muster");
+        assert_eq!(employee_name(&store, "/v1/contacts/8"), "/v1/contacts/8");
+    }
+}
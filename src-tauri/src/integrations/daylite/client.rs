@@ -1,4 +1,11 @@
-use super::shared::{normalize_base_url, DayliteApiError, DayliteApiErrorCode};
+use super::retry::{
+    is_retryable_error, is_retryable_status, parse_retry_after, request_slots, with_retry_count,
+    Retrier,
+};
+use super::shared::{
+    normalize_base_url, normalize_http_error, DayliteApiError, DayliteApiErrorCode,
+    DaylitePageProgress,
+};
 #[cfg(test)]
 use crate::integrations::http_record_replay::{
     RecordReplayConfig, RecordedInteraction, RecordedRequest, RecordedResponse, VcrMode,
@@ -7,23 +14,33 @@ use serde_json::Value;
 use std::future::Future;
 use std::pin::Pin;
 use tauri_plugin_http::reqwest;
-use tauri_plugin_http::reqwest::header::AUTHORIZATION;
+use tauri_plugin_http::reqwest::header::{AUTHORIZATION, RETRY_AFTER};
 
 pub(super) struct DayliteApiClient {
     transport: Box<dyn DayliteHttpTransport>,
+    page_progress: Option<Box<PageProgressSink>>,
+    retrier: Retrier,
 }
 
+pub(super) type PageProgressSink = dyn Fn(&DaylitePageProgress) + Send + Sync;
+
 impl DayliteApiClient {
     pub(super) fn new(base_url: &str) -> Result<Self, DayliteApiError> {
         let transport = ReqwestTransport::new(base_url)?;
         Ok(Self {
             transport: Box::new(transport),
+            page_progress: None,
+            retrier: Retrier::standard(),
         })
     }
 
     #[cfg(test)]
     pub(super) fn with_transport(transport: Box<dyn DayliteHttpTransport>) -> Self {
-        Self { transport }
+        Self {
+            transport,
+            page_progress: None,
+            retrier: Retrier::none(),
+        }
     }
 
     #[cfg(test)]
@@ -43,14 +60,77 @@ impl DayliteApiClient {
 
         Ok(Self {
             transport: Box::new(transport),
+            page_progress: None,
+            retrier: Retrier::none(),
         })
     }
 
+    /// Reports every page loaded while following `next` links.
+    pub(super) fn with_page_progress(
+        mut self,
+        sink: impl Fn(&DaylitePageProgress) + Send + Sync + 'static,
+    ) -> Self {
+        self.page_progress = Some(Box::new(sink));
+        self
+    }
+
+    pub(super) fn report_page_progress(&self, progress: &DaylitePageProgress) {
+        if let Some(sink) = &self.page_progress {
+            sink(progress);
+        }
+    }
+
+    #[cfg(test)]
+    pub(super) fn with_retrier(mut self, retrier: Retrier) -> Self {
+        self.retrier = retrier;
+        self
+    }
+
+    /// Sends the request, repeating it per the client's retry policy. Once retries were
+    /// needed, a final 429/5xx response is returned as an error carrying the retry count.
     pub(super) async fn send_request(
         &self,
         request: DayliteHttpRequest,
     ) -> Result<DayliteHttpResponse, DayliteApiError> {
-        self.transport.send(request).await
+        let mut retries = 0;
+        loop {
+            let result = {
+                let _slot = request_slots()
+                    .acquire()
+                    .await
+                    .expect("request semaphore is never closed");
+                self.transport.send(request.clone()).await
+            };
+            let retry_after = match &result {
+                Ok(response) if is_retryable_status(response.status) => Some(
+                    response
+                        .retry_after
+                        .as_deref()
+                        .and_then(|value| parse_retry_after(value, chrono::Utc::now())),
+                ),
+                Err(error) if is_retryable_error(error) => Some(None),
+                _ => None,
+            };
+            let delay = retry_after
+                .filter(|_| request.retryable)
+                .and_then(|after| self.retrier.delay_for(retries, after));
+
+            match (delay, result) {
+                (Some(delay), _) => {
+                    (self.retrier.sleep)(delay).await;
+                    retries += 1;
+                }
+                (None, result) if retries == 0 => return result,
+                (None, Ok(response)) if is_retryable_status(response.status) => {
+                    return Err(with_retry_count(
+                        normalize_http_error(response.status, &response.body, &request.path),
+                        retries,
+                    ));
+                }
+                (None, Ok(response)) => return Ok(response),
+                (None, Err(error)) => return Err(with_retry_count(error, retries)),
+            }
+        }
     }
 
     #[cfg(test)]
@@ -66,6 +146,8 @@ impl DayliteApiClient {
 
         Ok(Self {
             transport: Box::new(transport),
+            page_progress: None,
+            retrier: Retrier::none(),
         })
     }
 }
@@ -93,6 +175,9 @@ pub(super) struct DayliteHttpRequest {
     pub query: Vec<(String, String)>,
     pub body: Option<Value>,
     pub access_token: Option<String>,
+    // False for requests that must not be sent twice, such as creating a record: a
+    // timeout or 5xx can arrive after Daylite has already stored it.
+    pub retryable: bool,
 }
 
 impl DayliteHttpRequest {
@@ -103,6 +188,7 @@ impl DayliteHttpRequest {
             query: Vec::new(),
             body: None,
             access_token: None,
+            retryable: true,
         }
     }
 }
@@ -111,6 +197,8 @@ impl DayliteHttpRequest {
 pub(super) struct DayliteHttpResponse {
     pub status: u16,
     pub body: String,
+    // Raw Retry-After header, if the server sent one.
+    pub retry_after: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -202,6 +290,7 @@ impl DayliteHttpTransport for ReqwestTransport {
                     return Ok(DayliteHttpResponse {
                         status: response.status,
                         body: response.body,
+                        retry_after: None,
                     });
                 }
             }
@@ -260,6 +349,11 @@ impl DayliteHttpTransport for ReqwestTransport {
             })?;
 
             let status = response.status().as_u16();
+            let retry_after = response
+                .headers()
+                .get(RETRY_AFTER)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_string);
             let body = response.text().await.map_err(|error| {
                 DayliteApiError::new(
                     DayliteApiErrorCode::RequestFailed,
@@ -294,7 +388,11 @@ impl DayliteHttpTransport for ReqwestTransport {
                 }
             }
 
-            Ok(DayliteHttpResponse { status, body })
+            Ok(DayliteHttpResponse {
+                status,
+                body,
+                retry_after,
+            })
         })
     }
 }
@@ -353,6 +451,7 @@ mod tests {
                     query: vec![("full-records".to_string(), "true".to_string())],
                     body: None,
                     access_token: Some("top-secret-token".to_string()),
+                    retryable: true,
                 }),
                 response: RecordedResponse {
                     status: 200,
@@ -391,6 +490,7 @@ mod tests {
             query: vec![("full-records".to_string(), "true".to_string())],
             body: None,
             access_token: Some("ignored-in-replay".to_string()),
+            retryable: true,
         };
 
         let started_at = Instant::now();
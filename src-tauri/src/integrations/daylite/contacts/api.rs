@@ -1,4 +1,4 @@
-use super::ical_urls::merge_contact_ical_urls;
+use super::ical_urls::{merge_contact_ical_urls, reconcile_employee_calendars_from_contacts};
 use super::mapping::{
     filter_planning_contacts, is_planning_contact, map_cached_contact, map_daylite_contact_summary,
     map_planning_contact_to_cache_entry, sort_contacts,
@@ -12,11 +12,12 @@ use crate::integrations::daylite::auth_flow::{
 use crate::integrations::daylite::client::{
     DayliteApiClient, DayliteHttpMethod, DayliteHttpRequest,
 };
+use crate::integrations::daylite::pagination::search_all_pages;
+use crate::integrations::daylite::projects::dedup_by_reference;
 use crate::integrations::daylite::shared::{
-    with_token_refresh_lock, DayliteApiError, DayliteApiErrorCode, DayliteSearchResult,
-    DayliteTokenState,
+    with_token_refresh_lock, DayliteApiError, DayliteApiErrorCode, DayliteTokenState,
 };
-use crate::integrations::local_store::LocalStore;
+use crate::integrations::local_store::{LocalStore, PlanningContactSettings};
 use chrono::{SecondsFormat, Utc};
 use serde_json::json;
 
@@ -34,6 +35,33 @@ pub async fn sync_contact_ical_urls(
     Ok(())
 }
 
+/// Loads all planning contacts from Daylite without touching the store, so callers can
+/// apply them to a freshly loaded store afterwards.
+pub async fn fetch_planning_contacts(
+    daylite_base_url: &str,
+    settings: &PlanningContactSettings,
+) -> Result<Vec<PlanningContactRecord>, DayliteApiError> {
+    let client = DayliteApiClient::new(daylite_base_url)?;
+    with_token_refresh_lock(|tokens| list_contacts_core(&client, tokens, settings)).await
+}
+
+/// Replaces the cached contacts and reconciles the employees' calendar URLs with them.
+pub fn apply_contacts_to_store(store: &mut LocalStore, contacts: &[PlanningContactRecord]) {
+    let synced_at = current_timestamp_iso8601();
+    store.daylite_cache.last_synced_at = Some(synced_at.clone());
+    store.daylite_cache.contacts_synced_at = Some(synced_at);
+    store.daylite_cache.contacts = contacts
+        .iter()
+        .cloned()
+        .map(map_planning_contact_to_cache_entry)
+        .collect();
+    reconcile_employee_calendars_from_contacts(
+        &mut store.employee_settings,
+        contacts,
+        &store.planning_contacts,
+    );
+}
+
 pub(in crate::integrations::daylite) async fn update_contact_ical_urls_core(
     client: &DayliteApiClient,
     token_state: DayliteTokenState,
@@ -52,6 +80,7 @@ pub(in crate::integrations::daylite) async fn update_contact_ical_urls_core(
         current_contact.urls.clone(),
         &input.primary_ical_url,
         &input.absence_ical_url,
+        &store.planning_contacts,
     );
     let token_state = send_authenticated_request(
         client,
@@ -78,14 +107,17 @@ pub(in crate::integrations::daylite) async fn update_contact_ical_urls_core(
         .collect();
 
     cached_contacts.retain(|contact| contact.reference != updated_contact.reference);
-    if is_planning_contact(&updated_contact) {
+    if is_planning_contact(&updated_contact, &store.planning_contacts) {
         cached_contacts.push(updated_contact.clone());
     }
 
-    store.daylite_cache.contacts = sort_contacts(filter_planning_contacts(cached_contacts))
-        .into_iter()
-        .map(map_planning_contact_to_cache_entry)
-        .collect();
+    store.daylite_cache.contacts = sort_contacts(filter_planning_contacts(
+        cached_contacts,
+        &store.planning_contacts,
+    ))
+    .into_iter()
+    .map(map_planning_contact_to_cache_entry)
+    .collect();
 
     Ok((updated_contact, token_state))
 }
@@ -93,29 +125,36 @@ pub(in crate::integrations::daylite) async fn update_contact_ical_urls_core(
 pub(in crate::integrations::daylite) async fn list_contacts_core(
     client: &DayliteApiClient,
     token_state: DayliteTokenState,
+    settings: &PlanningContactSettings,
 ) -> Result<(Vec<PlanningContactRecord>, DayliteTokenState), DayliteApiError> {
-    let (search_result, token_state) =
-        send_authenticated_json::<DayliteSearchResult<DayliteContactSummary>>(
-            client,
-            token_state,
-            DayliteHttpRequest {
-                query: vec![("full-records".to_string(), "true".to_string())],
-                // A top-level array of clauses is matched with OR semantics; both
-                // planning categories are fetched in one call.
-                body: Some(json!([
-                    { "category": { "equal": "Monteur" } },
-                    { "category": { "equal": "Test" } },
-                ])),
-                ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/contacts/_search")
-            },
-        )
-        .await?;
+    let clauses: Vec<_> = settings
+        .planning_categories()
+        .into_iter()
+        .map(|category| json!({ "category": { "equal": category } }))
+        .collect();
+    let (pages, token_state) = search_all_pages::<DayliteContactSummary>(
+        client,
+        token_state,
+        DayliteHttpRequest {
+            query: vec![("full-records".to_string(), "true".to_string())],
+            // A top-level array of clauses is matched with OR semantics; all
+            // planning categories are fetched in one call.
+            body: Some(json!(clauses)),
+            ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/contacts/_search")
+        },
+        |_| false,
+    )
+    .await?;
     let contacts = sort_contacts(filter_planning_contacts(
-        search_result
-            .results
-            .into_iter()
-            .map(map_daylite_contact_summary)
-            .collect(),
+        dedup_by_reference(
+            pages
+                .results
+                .into_iter()
+                .map(map_daylite_contact_summary)
+                .collect(),
+            |contact| &contact.reference,
+        ),
+        settings,
     ));
 
     Ok((contacts, token_state))
@@ -156,9 +195,13 @@ mod tests {
             let transport = MockTransport::new(vec![Ok(search_response)]);
             let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
 
-            let (contacts, _) = list_contacts_core(&client, token_state("token", "refresh"))
-                .await
-                .expect("list should succeed");
+            let (contacts, _) = list_contacts_core(
+                &client,
+                token_state("token", "refresh"),
+                &PlanningContactSettings::default(),
+            )
+            .await
+            .expect("list should succeed");
 
             assert_eq!(contacts.len(), 2);
 
@@ -173,6 +216,67 @@ mod tests {
         });
     }
 
+    #[test]
+    fn list_contacts_searches_configured_categories() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![Ok(mock_response(
+                200,
+                r#"{"results":[{"self":"/v1/contacts/900","first_name":"Max","last_name":"M","category":"Elektriker","urls":[]},{"self":"/v1/contacts/901","first_name":"Bea","last_name":"T","category":"Monteur","urls":[]}]}"#,
+            ))]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let settings = PlanningContactSettings {
+                categories: vec!["Elektriker".to_string(), "Azubi".to_string()],
+                ..PlanningContactSettings::default()
+            };
+
+            let (contacts, _) =
+                list_contacts_core(&client, token_state("token", "refresh"), &settings)
+                    .await
+                    .expect("list should succeed");
+
+            assert_eq!(contacts.len(), 1);
+            assert_eq!(contacts[0].reference, "/v1/contacts/900");
+            assert_eq!(
+                transport.requests()[0].body,
+                Some(json!([
+                    { "category": { "equal": "Elektriker" } },
+                    { "category": { "equal": "Azubi" } },
+                ]))
+            );
+        });
+    }
+
+    #[test]
+    fn list_contacts_follows_next_links() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/contacts/900","first_name":"Max","last_name":"M","category":"Monteur","urls":[]}],"next":"/v1/contacts/_search?start=1"}"#,
+                )),
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/contacts/901","first_name":"Bea","last_name":"T","category":"Test","urls":[]}],"next":null}"#,
+                )),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+
+            let (contacts, _) = list_contacts_core(
+                &client,
+                token_state("token", "refresh"),
+                &PlanningContactSettings::default(),
+            )
+            .await
+            .expect("list should succeed");
+
+            assert_eq!(contacts.len(), 2);
+            let requests = transport.requests();
+            assert_eq!(requests.len(), 2);
+            assert_eq!(requests[1].path, "/contacts/_search");
+            assert_eq!(requests[1].body, requests[0].body);
+        });
+    }
+
     #[test]
     fn parse_contact_id_rejects_invalid_reference() {
         let error = parse_contact_id("/v1/contacts/not-a-number")
@@ -357,6 +461,7 @@ mod tests {
             let (contacts, token_state) = list_contacts_core(
                 &client,
                 token_state("replay-access-token", "replay-refresh-token"),
+                &PlanningContactSettings::default(),
             )
             .await
             .expect("list should replay from cassette");
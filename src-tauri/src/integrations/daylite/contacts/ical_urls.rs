@@ -1,11 +1,16 @@
 use super::types::PlanningContactRecord;
-use crate::integrations::local_store::{DayliteContactUrl, EmployeeSetting};
+use crate::integrations::local_store::{
+    DayliteContactUrl, EmployeeSetting, PlanningContactSettings,
+};
 
 pub(super) fn merge_contact_ical_urls(
     existing_urls: Vec<DayliteContactUrl>,
     primary_ical_url: &str,
     absence_ical_url: &str,
+    labels: &PlanningContactSettings,
 ) -> Vec<DayliteContactUrl> {
+    let primary_label = labels.primary_label();
+    let absence_label = labels.absence_label();
     let mut merged_urls = existing_urls
         .into_iter()
         .filter(|url| {
@@ -13,13 +18,13 @@ pub(super) fn merge_contact_ical_urls(
                 return true;
             };
 
-            !is_primary_ical_label(&label) && !is_absence_ical_label(&label)
+            !is_label(&label, primary_label) && !is_label(&label, absence_label)
         })
         .collect::<Vec<_>>();
 
     if let Some(primary_url) = normalize_non_empty(primary_ical_url) {
         merged_urls.push(DayliteContactUrl {
-            label: Some("Einsatz iCal".to_string()),
+            label: Some(primary_label.to_string()),
             url: Some(primary_url.to_string()),
             note: None,
         });
@@ -27,7 +32,7 @@ pub(super) fn merge_contact_ical_urls(
 
     if let Some(absence_url) = normalize_non_empty(absence_ical_url) {
         merged_urls.push(DayliteContactUrl {
-            label: Some("Abwesenheit iCal".to_string()),
+            label: Some(absence_label.to_string()),
             url: Some(absence_url.to_string()),
             note: None,
         });
@@ -42,10 +47,11 @@ pub(super) fn merge_contact_ical_urls(
 pub(super) fn reconcile_employee_calendars_from_contacts(
     settings: &mut Vec<EmployeeSetting>,
     contacts: &[PlanningContactRecord],
+    labels: &PlanningContactSettings,
 ) {
     for contact in contacts {
-        let primary = extract_managed_ical_url(&contact.urls, is_primary_ical_label);
-        let absence = extract_managed_ical_url(&contact.urls, is_absence_ical_label);
+        let primary = extract_managed_ical_url(&contact.urls, labels.primary_label());
+        let absence = extract_managed_ical_url(&contact.urls, labels.absence_label());
 
         if let Some(setting) = settings
             .iter_mut()
@@ -72,13 +78,10 @@ pub(super) fn reconcile_employee_calendars_from_contacts(
     }
 }
 
-fn extract_managed_ical_url(
-    urls: &[DayliteContactUrl],
-    matches_label: fn(&str) -> bool,
-) -> Option<String> {
+fn extract_managed_ical_url(urls: &[DayliteContactUrl], managed_label: &str) -> Option<String> {
     urls.iter().find_map(|url| {
         let label = normalize_url_label(url.label.as_deref())?;
-        if !matches_label(&label) {
+        if !is_label(&label, managed_label) {
             return None;
         }
 
@@ -89,12 +92,9 @@ fn extract_managed_ical_url(
     })
 }
 
-fn is_primary_ical_label(label: &str) -> bool {
-    label == "einsatz ical"
-}
-
-fn is_absence_ical_label(label: &str) -> bool {
-    label == "abwesenheit ical"
+/// `label` must already be normalized by [`normalize_url_label`].
+fn is_label(label: &str, managed_label: &str) -> bool {
+    label == managed_label.trim().to_lowercase()
 }
 
 fn normalize_url_label(value: Option<&str>) -> Option<String> {
@@ -141,6 +141,7 @@ mod tests {
             existing_urls,
             "https://example.com/new-primary.ics",
             "https://example.com/new-absence.ics",
+            &PlanningContactSettings::default(),
         );
 
         assert_eq!(
@@ -192,7 +193,11 @@ mod tests {
             ],
         }];
 
-        reconcile_employee_calendars_from_contacts(&mut settings, &contacts);
+        reconcile_employee_calendars_from_contacts(
+            &mut settings,
+            &contacts,
+            &PlanningContactSettings::default(),
+        );
 
         assert_eq!(settings.len(), 1);
         assert_eq!(settings[0].daylite_contact_reference, "/v1/contacts/100");
@@ -216,6 +221,9 @@ mod tests {
             primary_ical_last_test_passed: Some(true),
             absence_ical_last_tested_at: None,
             absence_ical_last_test_passed: None,
+            working_time_model: None,
+            holiday_subdivision: None,
+            employee_group: None,
         }];
         let contacts = vec![PlanningContactRecord {
             reference: "/v1/contacts/100".to_string(),
@@ -229,7 +237,11 @@ mod tests {
             }],
         }];
 
-        reconcile_employee_calendars_from_contacts(&mut settings, &contacts);
+        reconcile_employee_calendars_from_contacts(
+            &mut settings,
+            &contacts,
+            &PlanningContactSettings::default(),
+        );
 
         assert_eq!(
             settings[0].zep_primary_calendar,
@@ -249,6 +261,9 @@ mod tests {
             primary_ical_last_test_passed: Some(true),
             absence_ical_last_tested_at: None,
             absence_ical_last_test_passed: None,
+            working_time_model: None,
+            holiday_subdivision: None,
+            employee_group: None,
         }];
         let contacts = vec![PlanningContactRecord {
             reference: "/v1/contacts/100".to_string(),
@@ -258,7 +273,11 @@ mod tests {
             urls: vec![],
         }];
 
-        reconcile_employee_calendars_from_contacts(&mut settings, &contacts);
+        reconcile_employee_calendars_from_contacts(
+            &mut settings,
+            &contacts,
+            &PlanningContactSettings::default(),
+        );
 
         assert_eq!(settings[0].zep_primary_calendar, None);
         assert_eq!(settings[0].zep_absence_calendar, None);
@@ -275,6 +294,9 @@ mod tests {
             primary_ical_last_test_passed: Some(true),
             absence_ical_last_tested_at: None,
             absence_ical_last_test_passed: None,
+            working_time_model: None,
+            holiday_subdivision: None,
+            employee_group: None,
         }];
         let contacts = vec![PlanningContactRecord {
             reference: "/v1/contacts/100".to_string(),
@@ -288,7 +310,11 @@ mod tests {
             }],
         }];
 
-        reconcile_employee_calendars_from_contacts(&mut settings, &contacts);
+        reconcile_employee_calendars_from_contacts(
+            &mut settings,
+            &contacts,
+            &PlanningContactSettings::default(),
+        );
 
         assert_eq!(
             settings[0].primary_ical_last_tested_at,
@@ -312,8 +338,64 @@ mod tests {
             }],
         }];
 
-        reconcile_employee_calendars_from_contacts(&mut settings, &contacts);
+        reconcile_employee_calendars_from_contacts(
+            &mut settings,
+            &contacts,
+            &PlanningContactSettings::default(),
+        );
 
         assert!(settings.is_empty());
     }
+
+    #[test]
+    fn configured_labels_are_merged_and_reconciled() {
+        let labels = PlanningContactSettings {
+            primary_ical_label: "Baustelle iCal".to_string(),
+            absence_ical_label: "Fehlzeiten iCal".to_string(),
+            ..PlanningContactSettings::default()
+        };
+        let existing_urls = vec![
+            DayliteContactUrl {
+                label: Some("Einsatz iCal".to_string()),
+                url: Some("https://example.com/other.ics".to_string()),
+                note: None,
+            },
+            DayliteContactUrl {
+                label: Some(" baustelle ical ".to_string()),
+                url: Some("https://example.com/old-primary.ics".to_string()),
+                note: None,
+            },
+        ];
+
+        let urls = merge_contact_ical_urls(
+            existing_urls,
+            "https://example.com/new-primary.ics",
+            "https://example.com/new-absence.ics",
+            &labels,
+        );
+
+        assert_eq!(urls.len(), 3);
+        assert_eq!(urls[0].label.as_deref(), Some("Einsatz iCal"));
+        assert_eq!(urls[1].label.as_deref(), Some("Baustelle iCal"));
+        assert_eq!(urls[2].label.as_deref(), Some("Fehlzeiten iCal"));
+
+        let mut settings: Vec<EmployeeSetting> = vec![];
+        let contacts = vec![PlanningContactRecord {
+            reference: "/v1/contacts/100".to_string(),
+            full_name: None,
+            nickname: None,
+            category: Some("Elektriker".to_string()),
+            urls,
+        }];
+        reconcile_employee_calendars_from_contacts(&mut settings, &contacts, &labels);
+
+        assert_eq!(
+            settings[0].zep_primary_calendar.as_deref(),
+            Some("https://example.com/new-primary.ics")
+        );
+        assert_eq!(
+            settings[0].zep_absence_calendar.as_deref(),
+            Some("https://example.com/new-absence.ics")
+        );
+    }
 }
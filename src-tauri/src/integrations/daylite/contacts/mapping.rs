@@ -1,5 +1,7 @@
 use super::types::{DayliteContactSummary, PlanningContactRecord};
-use crate::integrations::local_store::{DayliteContactCacheEntry, DayliteContactUrl};
+use crate::integrations::local_store::{
+    DayliteContactCacheEntry, DayliteContactUrl, PlanningContactSettings,
+};
 
 pub(super) fn map_daylite_contact_summary(contact: DayliteContactSummary) -> PlanningContactRecord {
     let full_name = normalize_string_option(contact.full_name)
@@ -38,18 +40,33 @@ pub(super) fn map_planning_contact_to_cache_entry(
 
 pub(super) fn filter_planning_contacts(
     contacts: Vec<PlanningContactRecord>,
+    settings: &PlanningContactSettings,
 ) -> Vec<PlanningContactRecord> {
     contacts
         .into_iter()
-        .filter(is_planning_contact)
+        .filter(|contact| is_planning_contact(contact, settings))
         .collect::<Vec<_>>()
 }
 
-pub(super) fn is_planning_contact(contact: &PlanningContactRecord) -> bool {
-    normalize_string_option(contact.category.clone())
+pub(super) fn is_planning_contact(
+    contact: &PlanningContactRecord,
+    settings: &PlanningContactSettings,
+) -> bool {
+    is_planning_category(contact.category.clone(), settings)
+}
+
+/// Whether a contact of this category is an employee that can be planned.
+pub(in crate::integrations::daylite) fn is_planning_category(
+    category: Option<String>,
+    settings: &PlanningContactSettings,
+) -> bool {
+    normalize_string_option(category)
         .map(|category| {
             let category = category.to_lowercase();
-            category == "monteur" || category == "test"
+            settings
+                .planning_categories()
+                .iter()
+                .any(|planning| planning.to_lowercase() == category)
         })
         .unwrap_or(false)
 }
@@ -235,11 +252,33 @@ mod tests {
             },
         ];
 
-        let mapped = sort_contacts(filter_planning_contacts(contacts));
+        let mapped = sort_contacts(filter_planning_contacts(
+            contacts,
+            &PlanningContactSettings::default(),
+        ));
 
         assert_eq!(mapped.len(), 3);
         assert_eq!(mapped[0].reference, "/v1/contacts/3004"); // Bea Test
         assert_eq!(mapped[1].reference, "/v1/contacts/3003"); // Maks
         assert_eq!(mapped[2].reference, "/v1/contacts/3001"); // Zora Monteur
     }
+
+    #[test]
+    fn planning_categories_come_from_settings() {
+        let settings = PlanningContactSettings {
+            categories: vec!["Elektriker".to_string(), " Azubi ".to_string()],
+            ..PlanningContactSettings::default()
+        };
+
+        assert!(is_planning_category(
+            Some(" elektriker ".to_string()),
+            &settings
+        ));
+        assert!(is_planning_category(Some("AZUBI".to_string()), &settings));
+        assert!(!is_planning_category(
+            Some("Monteur".to_string()),
+            &settings
+        ));
+        assert!(!is_planning_category(None, &settings));
+    }
 }
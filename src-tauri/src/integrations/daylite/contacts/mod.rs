@@ -4,8 +4,10 @@ mod ical_urls;
 mod mapping;
 pub mod types;
 
-pub use api::sync_contact_ical_urls;
+pub use api::{apply_contacts_to_store, fetch_planning_contacts, sync_contact_ical_urls};
 pub use types::DayliteUpdateContactIcalUrlsInput;
 
+pub(in crate::integrations::daylite) use mapping::is_planning_category;
+
 #[cfg(test)]
 pub(in crate::integrations::daylite) use api::{list_contacts_core, update_contact_ical_urls_core};
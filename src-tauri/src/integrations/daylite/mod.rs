@@ -1,10 +1,17 @@
+pub mod assignment_mirror;
 pub mod auth;
 mod auth_flow;
 mod client;
 pub mod contacts;
+mod pagination;
+pub mod project_cache;
+pub mod project_details;
+pub mod project_search;
+pub mod project_status;
 pub mod projects;
 #[cfg(test)]
 mod recording_harness;
+mod retry;
 pub mod shared;
 #[cfg(test)]
 mod test_support;
@@ -0,0 +1,318 @@
+use super::auth_flow::send_authenticated_json;
+use super::client::{DayliteApiClient, DayliteHttpRequest};
+use super::shared::{
+    DayliteApiError, DayliteApiErrorCode, DaylitePageProgress, DayliteSearchResult,
+    DayliteTokenState,
+};
+use serde::de::DeserializeOwned;
+use std::collections::HashSet;
+use tauri_plugin_http::reqwest;
+
+// Far above any realistic database size at Daylite's default page size; reaching it means
+// the server keeps handing out links, and a partial list must not pass as complete.
+pub(super) const MAX_SEARCH_PAGES: u32 = 200;
+
+pub(super) struct SearchPages<T> {
+    pub results: Vec<T>,
+    // Link to the first page that was not loaded because `has_enough` was satisfied.
+    pub next: Option<String>,
+}
+
+/// Sends `request` and follows the `next` links of the responses, reporting every page to
+/// the client's progress sink. Stops early once `has_enough` returns true for the number of
+/// results loaded so far. Results keep the server's order across pages.
+pub(super) async fn search_all_pages<T: DeserializeOwned>(
+    client: &DayliteApiClient,
+    token_state: DayliteTokenState,
+    request: DayliteHttpRequest,
+    has_enough: impl Fn(usize) -> bool,
+) -> Result<(SearchPages<T>, DayliteTokenState), DayliteApiError> {
+    let path = request.path.clone();
+    let mut token_state = token_state;
+    let mut next_request = Some(request.clone());
+    let mut seen_links = HashSet::new();
+    let mut results = Vec::new();
+    let mut page = 0;
+
+    while let Some(page_request) = next_request.take() {
+        if page == MAX_SEARCH_PAGES {
+            return Err(too_many_pages_error(
+                &path,
+                format!("Seitenlimit von {MAX_SEARCH_PAGES} Seiten erreicht"),
+            ));
+        }
+        let (search_result, updated_tokens) =
+            send_authenticated_json::<DayliteSearchResult<T>>(client, token_state, page_request)
+                .await?;
+        token_state = updated_tokens;
+        page += 1;
+        results.extend(search_result.results);
+        client.report_page_progress(&DaylitePageProgress {
+            path: path.clone(),
+            page,
+            loaded: results.len() as u32,
+        });
+
+        let Some(link) = search_result
+            .next
+            .map(|link| link.trim().to_string())
+            .filter(|link| !link.is_empty())
+        else {
+            break;
+        };
+        if has_enough(results.len()) {
+            return Ok((
+                SearchPages {
+                    results,
+                    next: Some(link),
+                },
+                token_state,
+            ));
+        }
+        if !seen_links.insert(link.clone()) {
+            return Err(too_many_pages_error(
+                &path,
+                format!("next-Link `{link}` wurde wiederholt geliefert"),
+            ));
+        }
+        next_request = Some(next_page_request(&request, &link).ok_or_else(|| {
+            DayliteApiError::new(
+                DayliteApiErrorCode::InvalidResponse,
+                None,
+                "Die Antwort von Daylite konnte nicht verarbeitet werden.",
+                format!("Ungültiger next-Link `{link}` für {path}"),
+            )
+        })?);
+    }
+
+    Ok((
+        SearchPages {
+            results,
+            next: None,
+        },
+        token_state,
+    ))
+}
+
+/// The request for a `next` link such as "/v1/projects/_search?start=50": same method and
+/// body, the link's path relative to the versioned base URL, and the original query
+/// parameters overridden by those of the link.
+fn next_page_request(first: &DayliteHttpRequest, link: &str) -> Option<DayliteHttpRequest> {
+    let url = if link.starts_with("http://") || link.starts_with("https://") {
+        reqwest::Url::parse(link).ok()?
+    } else if link.starts_with('/') {
+        reqwest::Url::parse(&format!("http://daylite.invalid{link}")).ok()?
+    } else {
+        return None;
+    };
+
+    let path = url.path();
+    let path = path.strip_prefix("/v1").unwrap_or(path);
+    if path.is_empty() {
+        return None;
+    }
+    let link_query: Vec<(String, String)> = url.query_pairs().into_owned().collect();
+    let mut query: Vec<(String, String)> = first
+        .query
+        .iter()
+        .filter(|(key, _)| !link_query.iter().any(|(link_key, _)| link_key == key))
+        .cloned()
+        .collect();
+    query.extend(link_query);
+
+    Some(DayliteHttpRequest {
+        path: path.to_string(),
+        query,
+        ..first.clone()
+    })
+}
+
+fn too_many_pages_error(path: &str, reason: String) -> DayliteApiError {
+    DayliteApiError::new(
+        DayliteApiErrorCode::InvalidResponse,
+        None,
+        "Daylite hat zu viele Ergebnisseiten geliefert; die Liste konnte nicht vollständig geladen werden.",
+        format!("Paginierung für {path} abgebrochen: {reason}"),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::client::DayliteHttpMethod;
+    use super::super::recording_harness::{
+        paged_search_request, PAGED_SEARCH_CASSETTE, PAGED_SEARCH_MAX_RESULTS,
+    };
+    use super::super::test_support::{
+        mock_response, token_state, valid_token_state, MockTransport,
+    };
+    use super::*;
+    use serde::Deserialize;
+    use serde_json::json;
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Debug, Deserialize, PartialEq)]
+    struct Item {
+        #[serde(rename = "self")]
+        reference: String,
+    }
+
+    fn search_request() -> DayliteHttpRequest {
+        DayliteHttpRequest {
+            query: vec![
+                ("full-records".to_string(), "true".to_string()),
+                ("limit".to_string(), "2".to_string()),
+            ],
+            body: Some(json!({ "name": { "contains": "Nord" } })),
+            ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/projects/_search")
+        }
+    }
+
+    #[test]
+    fn next_link_keeps_body_and_overrides_query() {
+        let request = next_page_request(
+            &search_request(),
+            "/v1/projects/_search?limit=2&start=%2Fv1%2Fprojects%2F7",
+        )
+        .expect("link should be valid");
+
+        assert_eq!(request.method, DayliteHttpMethod::Post);
+        assert_eq!(request.path, "/projects/_search");
+        assert_eq!(
+            request.query,
+            vec![
+                ("full-records".to_string(), "true".to_string()),
+                ("limit".to_string(), "2".to_string()),
+                ("start".to_string(), "/v1/projects/7".to_string()),
+            ]
+        );
+        assert_eq!(request.body, search_request().body);
+    }
+
+    #[test]
+    fn absolute_next_links_use_the_configured_host() {
+        let request = next_page_request(
+            &search_request(),
+            "https://api.marketcircle.net/v1/contacts/_search?start=40",
+        )
+        .expect("link should be valid");
+
+        assert_eq!(request.path, "/contacts/_search");
+        assert!(next_page_request(&search_request(), "start=40").is_none());
+    }
+
+    #[test]
+    fn follows_next_links_and_reports_progress() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/projects/1"},{"self":"/v1/projects/2"}],"next":"/v1/projects/_search?start=2"}"#,
+                )),
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/projects/3"}],"next":null}"#,
+                )),
+            ]);
+            let progress = Arc::new(Mutex::new(Vec::new()));
+            let sink = Arc::clone(&progress);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()))
+                .with_page_progress(move |p| sink.lock().unwrap().push(p.clone()));
+
+            let (pages, _) =
+                search_all_pages::<Item>(&client, valid_token_state(), search_request(), |_| false)
+                    .await
+                    .expect("pagination should succeed");
+
+            let references: Vec<&str> = pages
+                .results
+                .iter()
+                .map(|item| item.reference.as_str())
+                .collect();
+            assert_eq!(
+                references,
+                vec!["/v1/projects/1", "/v1/projects/2", "/v1/projects/3"]
+            );
+            assert_eq!(pages.next, None);
+            let requests = transport.requests();
+            assert_eq!(requests.len(), 2);
+            assert!(requests[1]
+                .query
+                .contains(&("start".to_string(), "2".to_string())));
+            let progress = progress.lock().unwrap();
+            assert_eq!(progress.len(), 2);
+            assert_eq!(progress[1].page, 2);
+            assert_eq!(progress[1].loaded, 3);
+        });
+    }
+
+    #[test]
+    fn stops_early_and_returns_the_remaining_link() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![Ok(mock_response(
+                200,
+                r#"{"results":[{"self":"/v1/projects/1"},{"self":"/v1/projects/2"}],"next":"/v1/projects/_search?start=2"}"#,
+            ))]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+
+            let (pages, _) =
+                search_all_pages::<Item>(&client, valid_token_state(), search_request(), |n| {
+                    n >= 2
+                })
+                .await
+                .expect("pagination should succeed");
+
+            assert_eq!(pages.results.len(), 2);
+            assert_eq!(pages.next.as_deref(), Some("/v1/projects/_search?start=2"));
+            assert_eq!(transport.requests().len(), 1);
+        });
+    }
+
+    #[test]
+    fn repeated_next_link_fails_instead_of_looping() {
+        tauri::async_runtime::block_on(async {
+            let page =
+                r#"{"results":[{"self":"/v1/projects/1"}],"next":"/v1/projects/_search?start=1"}"#;
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(200, page)),
+                Ok(mock_response(200, page)),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport));
+
+            let error =
+                search_all_pages::<Item>(&client, valid_token_state(), search_request(), |_| false)
+                    .await
+                    .err()
+                    .expect("a repeated link should fail");
+
+            assert_eq!(error.code, DayliteApiErrorCode::InvalidResponse);
+            assert!(error.technical_message.contains("wiederholt"));
+        });
+    }
+
+    #[test]
+    // Recorded by `record_daylite_cassettes_from_live_api` (ADR 0010).
+    #[ignore = "cassette not recorded"]
+    fn paged_search_replays_vcr_cassette() {
+        tauri::async_runtime::block_on(async {
+            let client = DayliteApiClient::with_replay_cassette(PAGED_SEARCH_CASSETTE)
+                .expect("replay client should be created");
+
+            let (pages, _) = search_all_pages::<Item>(
+                &client,
+                token_state("replay-access-token", "replay-refresh-token"),
+                paged_search_request(),
+                |loaded| loaded >= PAGED_SEARCH_MAX_RESULTS,
+            )
+            .await
+            .expect("paged search should replay from cassette");
+
+            // More results than one page holds, so at least one next link was followed.
+            assert!(pages.results.len() > 2);
+            assert!(pages.results.len() <= PAGED_SEARCH_MAX_RESULTS);
+            assert!(pages
+                .results
+                .iter()
+                .all(|item| item.reference.starts_with("/v1/projects/")));
+        });
+    }
+}
@@ -0,0 +1,358 @@
+use super::client::{DayliteApiClient, DayliteHttpMethod, DayliteHttpRequest};
+use super::pagination::search_all_pages;
+use super::projects::{
+    dedup_by_reference, extract_numeric_id, list_projects_core, map_daylite_project_summary,
+    project_status_to_string, DayliteProjectSummaryDto, PlanningProjectRecord,
+};
+use super::shared::{
+    load_store_or_error, page_progress_emitter, save_store_or_error, with_token_refresh_lock,
+    DayliteApiError, DayliteTokenState,
+};
+use crate::integrations::local_store::{DayliteCache, DayliteProjectCacheEntry, LocalStore};
+use chrono::{DateTime, Duration, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use specta::Type;
+use std::collections::HashSet;
+
+// Incremental syncs look this far behind the last sync start, so changes saved while it ran
+// or stamped by a Daylite clock running slightly behind ours are not missed.
+const SYNC_OVERLAP_MINUTES: i64 = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DayliteProjectCacheSyncResult {
+    // True when every project was loaded, false for an incremental sync.
+    pub full: bool,
+    pub updated: u32,
+    pub removed: u32,
+    pub total: u32,
+    pub synced_at: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct DayliteProjectReferenceDto {
+    #[serde(rename = "self")]
+    reference: String,
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn daylite_sync_project_cache(
+    app: tauri::AppHandle,
+) -> Result<DayliteProjectCacheSyncResult, DayliteApiError> {
+    let mut store = load_store_or_error(app.clone())?;
+    let client = DayliteApiClient::new(&store.api_endpoints.daylite_base_url)?
+        .with_page_progress(page_progress_emitter(app.clone()));
+
+    let result = with_token_refresh_lock(|tokens| {
+        sync_project_cache_core(&client, tokens, &mut store.daylite_cache, Utc::now())
+    })
+    .await?;
+    save_store_or_error(app, store)?;
+
+    Ok(result)
+}
+
+/// Brings `store.daylite_cache.projects` up to date without progress reporting; the caller
+/// saves the store.
+pub async fn sync_project_cache(
+    store: &mut LocalStore,
+) -> Result<DayliteProjectCacheSyncResult, DayliteApiError> {
+    let client = DayliteApiClient::new(&store.api_endpoints.daylite_base_url)?;
+    with_token_refresh_lock(|tokens| {
+        sync_project_cache_core(&client, tokens, &mut store.daylite_cache, Utc::now())
+    })
+    .await
+}
+
+/// Loads every project on the first run (or when the cache is empty). Afterwards only
+/// projects modified since the last sync are fetched in full; deletions are found by
+/// comparing the cache with the list of all project references, which Daylite returns
+/// without full records.
+pub(super) async fn sync_project_cache_core(
+    client: &DayliteApiClient,
+    token_state: DayliteTokenState,
+    cache: &mut DayliteCache,
+    now: DateTime<Utc>,
+) -> Result<(DayliteProjectCacheSyncResult, DayliteTokenState), DayliteApiError> {
+    let synced_at = now.to_rfc3339_opts(SecondsFormat::Millis, true);
+    let last_sync = cache
+        .projects_synced_at
+        .as_deref()
+        .and_then(|value| DateTime::parse_from_rfc3339(value).ok())
+        .filter(|_| !cache.projects.is_empty());
+
+    let (result, token_state) = match last_sync {
+        None => {
+            let (projects, token_state) = list_projects_core(client, token_state).await?;
+            let previous: HashSet<String> = cache
+                .projects
+                .drain(..)
+                .map(|entry| entry.reference)
+                .collect();
+            cache.projects = projects
+                .into_iter()
+                .map(map_project_to_cache_entry)
+                .collect();
+            let current: HashSet<&str> = cache
+                .projects
+                .iter()
+                .map(|entry| entry.reference.as_str())
+                .collect();
+            let removed = previous
+                .iter()
+                .filter(|reference| !current.contains(reference.as_str()))
+                .count();
+            (
+                DayliteProjectCacheSyncResult {
+                    full: true,
+                    updated: cache.projects.len() as u32,
+                    removed: removed as u32,
+                    total: cache.projects.len() as u32,
+                    synced_at: synced_at.clone(),
+                },
+                token_state,
+            )
+        }
+        Some(last_sync) => {
+            let since = last_sync.with_timezone(&Utc) - Duration::minutes(SYNC_OVERLAP_MINUTES);
+            let (references, token_state) = list_project_references(client, token_state).await?;
+            let (changed, token_state) =
+                list_projects_modified_since(client, token_state, since).await?;
+
+            let before = cache.projects.len();
+            cache
+                .projects
+                .retain(|entry| references.contains(&entry.reference));
+            let removed = before - cache.projects.len();
+            let updated = changed.len();
+            for project in changed {
+                let entry = map_project_to_cache_entry(project);
+                match cache
+                    .projects
+                    .iter_mut()
+                    .find(|cached| cached.reference == entry.reference)
+                {
+                    Some(cached) => *cached = entry,
+                    None => cache.projects.push(entry),
+                }
+            }
+            cache
+                .projects
+                .sort_by_key(|entry| extract_numeric_id(&entry.reference));
+            (
+                DayliteProjectCacheSyncResult {
+                    full: false,
+                    updated: updated as u32,
+                    removed: removed as u32,
+                    total: cache.projects.len() as u32,
+                    synced_at: synced_at.clone(),
+                },
+                token_state,
+            )
+        }
+    };
+
+    cache.projects_synced_at = Some(synced_at.clone());
+    cache.last_synced_at = Some(synced_at);
+    Ok((result, token_state))
+}
+
+async fn list_project_references(
+    client: &DayliteApiClient,
+    token_state: DayliteTokenState,
+) -> Result<(HashSet<String>, DayliteTokenState), DayliteApiError> {
+    let (pages, token_state) = search_all_pages::<DayliteProjectReferenceDto>(
+        client,
+        token_state,
+        DayliteHttpRequest {
+            body: Some(json!({})),
+            ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/projects/_search")
+        },
+        |_| false,
+    )
+    .await?;
+
+    Ok((
+        pages
+            .results
+            .into_iter()
+            .map(|project| project.reference.trim().to_string())
+            .collect(),
+        token_state,
+    ))
+}
+
+async fn list_projects_modified_since(
+    client: &DayliteApiClient,
+    token_state: DayliteTokenState,
+    since: DateTime<Utc>,
+) -> Result<(Vec<PlanningProjectRecord>, DayliteTokenState), DayliteApiError> {
+    let (pages, token_state) = search_all_pages::<DayliteProjectSummaryDto>(
+        client,
+        token_state,
+        DayliteHttpRequest {
+            query: vec![("full-records".to_string(), "true".to_string())],
+            body: Some(json!({
+                "modify_date": {
+                    "greater_than": since.to_rfc3339_opts(SecondsFormat::Secs, true)
+                }
+            })),
+            ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/projects/_search")
+        },
+        |_| false,
+    )
+    .await?;
+
+    Ok((
+        dedup_by_reference(
+            pages
+                .results
+                .into_iter()
+                .map(map_daylite_project_summary)
+                .collect(),
+            |project| &project.reference,
+        ),
+        token_state,
+    ))
+}
+
+fn map_project_to_cache_entry(project: PlanningProjectRecord) -> DayliteProjectCacheEntry {
+    DayliteProjectCacheEntry {
+        status: project_status_to_string(&project.status).to_string(),
+        reference: project.reference,
+        name: project.name,
+        category: project.category,
+        due: project.due,
+        keywords: project.keywords,
+        modify_date: project.modify_date,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::daylite::test_support::{
+        mock_response, valid_token_state, MockTransport,
+    };
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-03-02T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn cached(reference: &str, name: &str) -> DayliteProjectCacheEntry {
+        DayliteProjectCacheEntry {
+            reference: reference.to_string(),
+            name: name.to_string(),
+            status: "new_status".to_string(),
+            ..DayliteProjectCacheEntry::default()
+        }
+    }
+
+    #[test]
+    fn first_sync_loads_all_projects_with_details() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![Ok(mock_response(
+                200,
+                r#"{"results":[{"self":"/v1/projects/2","name":"Projekt Süd","status":"in_progress","category":"Montage","keywords":["Dach"],"due":"2026-04-01T00:00:00Z","modify_date":"2026-03-01T08:00:00Z"},{"self":"/v1/projects/1","name":"Projekt Nord","status":"new_status"}]}"#,
+            ))]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let mut cache = DayliteCache::default();
+
+            let (result, _) =
+                sync_project_cache_core(&client, valid_token_state(), &mut cache, now())
+                    .await
+                    .expect("sync should succeed");
+
+            assert!(result.full);
+            assert_eq!(result.total, 2);
+            assert_eq!(cache.projects[0].reference, "/v1/projects/1");
+            let sued = &cache.projects[1];
+            assert_eq!(sued.status, "in_progress");
+            assert_eq!(sued.category.as_deref(), Some("Montage"));
+            assert_eq!(sued.due.as_deref(), Some("2026-04-01T00:00:00.000Z"));
+            assert_eq!(sued.keywords, vec!["Dach"]);
+            assert_eq!(
+                cache.projects_synced_at.as_deref(),
+                Some("2026-03-02T10:00:00.000Z")
+            );
+            let requests = transport.requests();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].body, Some(json!({})));
+        });
+    }
+
+    #[test]
+    fn incremental_sync_fetches_modified_projects_and_drops_deleted_ones() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/projects/1"},{"self":"/v1/projects/3"}]}"#,
+                )),
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/projects/3","name":"Projekt Ost","status":"new_status"},{"self":"/v1/projects/1","name":"Projekt Nord (neu)","status":"done"}]}"#,
+                )),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let mut cache = DayliteCache {
+                projects_synced_at: Some("2026-03-02T09:00:00.000Z".to_string()),
+                projects: vec![
+                    cached("/v1/projects/1", "Projekt Nord"),
+                    cached("/v1/projects/2", "Projekt Süd"),
+                ],
+                ..DayliteCache::default()
+            };
+
+            let (result, _) =
+                sync_project_cache_core(&client, valid_token_state(), &mut cache, now())
+                    .await
+                    .expect("sync should succeed");
+
+            assert!(!result.full);
+            assert_eq!((result.updated, result.removed, result.total), (2, 1, 2));
+            let names: Vec<&str> = cache.projects.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(names, vec!["Projekt Nord (neu)", "Projekt Ost"]);
+            assert_eq!(cache.projects[0].status, "done");
+
+            let requests = transport.requests();
+            assert_eq!(requests.len(), 2);
+            assert!(requests[0].query.is_empty());
+            assert_eq!(
+                requests[1].body,
+                Some(json!({ "modify_date": { "greater_than": "2026-03-02T08:55:00Z" } }))
+            );
+            assert!(requests[1]
+                .query
+                .contains(&("full-records".to_string(), "true".to_string())));
+        });
+    }
+
+    #[test]
+    fn failed_sync_keeps_the_previous_timestamp() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![Ok(mock_response(500, "{}"))]);
+            let client = DayliteApiClient::with_transport(Box::new(transport));
+            let mut cache = DayliteCache {
+                projects_synced_at: Some("2026-03-02T09:00:00.000Z".to_string()),
+                projects: vec![cached("/v1/projects/1", "Projekt Nord")],
+                ..DayliteCache::default()
+            };
+
+            assert!(
+                sync_project_cache_core(&client, valid_token_state(), &mut cache, now())
+                    .await
+                    .is_err()
+            );
+            assert_eq!(
+                cache.projects_synced_at.as_deref(),
+                Some("2026-03-02T09:00:00.000Z")
+            );
+            assert_eq!(cache.projects.len(), 1);
+        });
+    }
+}
@@ -0,0 +1,396 @@
+use super::projects::{extract_numeric_id, DateRange, DayliteProjectSummary};
+use super::shared::{
+    load_store_or_error, DayliteApiError, DayliteApiErrorCode, DayliteSearchInput,
+    DayliteSearchResult, DayliteSearchSort,
+};
+use crate::integrations::local_store::DayliteProjectCacheEntry;
+use std::cmp::Reverse;
+
+// Points per query token, by where it matched. Every token has to match somewhere.
+const EXACT_NAME_SCORE: u32 = 4;
+const PREFIX_NAME_SCORE: u32 = 3;
+const DETAIL_SCORE: u32 = 2;
+const SUBSTRING_NAME_SCORE: u32 = 1;
+// Extra points when the whole query starts the project name, e.g. "projekt n".
+const NAME_PREFIX_BONUS: u32 = 2;
+
+/// Searches the cached projects only; answers instantly and works offline. The UI shows
+/// these results while [`super::projects::daylite_search_projects`] is still running.
+#[tauri::command]
+#[specta::specta]
+pub fn daylite_search_cached_projects(
+    app: tauri::AppHandle,
+    input: DayliteSearchInput,
+) -> Result<DayliteSearchResult<DayliteProjectSummary>, DayliteApiError> {
+    let store = load_store_or_error(app)?;
+    search_cached_projects(&store.daylite_cache.projects, &input)
+}
+
+/// Errors that mean Daylite could not be reached at all, as opposed to an answer the user
+/// has to act on.
+pub(super) fn is_offline_error(error: &DayliteApiError) -> bool {
+    error.http_status.is_none()
+        && matches!(
+            error.code,
+            DayliteApiErrorCode::RequestFailed | DayliteApiErrorCode::Timeout
+        )
+}
+
+/// Matches every token of the search term against the project names, case-insensitively and
+/// with umlauts written either way ("Müller", "Mueller", "Muller"). Results are ranked by
+/// match quality, then active projects before finished ones, then the most recently modified.
+/// The cache does not know linked companies, so `company_reference` is not applied here.
+pub(super) fn search_cached_projects(
+    projects: &[DayliteProjectCacheEntry],
+    input: &DayliteSearchInput,
+) -> Result<DayliteSearchResult<DayliteProjectSummary>, DayliteApiError> {
+    let query_tokens = tokens(&input.search_term);
+    let query = query_tokens.join(" ");
+    let due = DateRange::parse(&input.due_from, &input.due_to)?;
+    let modified = DateRange::parse(&input.modified_from, &input.modified_to)?;
+    let category = input
+        .category
+        .as_deref()
+        .map(|category| category.trim().to_lowercase())
+        .filter(|category| !category.is_empty());
+    let keywords: Vec<String> = input
+        .keywords
+        .iter()
+        .flatten()
+        .map(|keyword| keyword.trim().to_lowercase())
+        .filter(|keyword| !keyword.is_empty())
+        .collect();
+
+    let mut matches: Vec<(u32, &DayliteProjectCacheEntry)> = projects
+        .iter()
+        .filter(|project| {
+            input
+                .statuses
+                .as_ref()
+                .is_none_or(|statuses| statuses.is_empty() || statuses.contains(&project.status))
+        })
+        .filter(|project| {
+            category.as_ref().is_none_or(|category| {
+                project
+                    .category
+                    .as_deref()
+                    .is_some_and(|value| value.trim().to_lowercase() == *category)
+            })
+        })
+        .filter(|project| {
+            keywords.is_empty()
+                || project
+                    .keywords
+                    .iter()
+                    .any(|keyword| keywords.contains(&keyword.to_lowercase()))
+        })
+        .filter(|project| {
+            due.contains(project.due.as_deref())
+                && modified.contains(project.modify_date.as_deref())
+        })
+        .filter_map(|project| {
+            score_project(project, &query_tokens, &query).map(|score| (score, project))
+        })
+        .collect();
+
+    match input.sort {
+        Some(DayliteSearchSort::Name) => matches.sort_by(|(_, a), (_, b)| a.name.cmp(&b.name)),
+        _ => matches.sort_by_key(|(score, project)| {
+            (
+                Reverse(*score),
+                status_rank(&project.status),
+                Reverse(project.modify_date.clone()),
+                extract_numeric_id(&project.reference),
+            )
+        }),
+    }
+    if let Some(limit) = input.limit {
+        matches.truncate(limit as usize);
+    }
+
+    Ok(DayliteSearchResult {
+        results: matches
+            .into_iter()
+            .map(|(_, project)| map_cache_entry_to_summary(project))
+            .collect(),
+        next: None,
+        from_cache: true,
+    })
+}
+
+fn score_project(
+    project: &DayliteProjectCacheEntry,
+    query_tokens: &[String],
+    query: &str,
+) -> Option<u32> {
+    let name_forms = [tokens(&project.name), tokens(&strip_umlauts(&project.name))];
+    let details: Vec<String> = project
+        .category
+        .iter()
+        .chain(&project.keywords)
+        .flat_map(|value| [tokens(value), tokens(&strip_umlauts(value))])
+        .flatten()
+        .collect();
+    let id = extract_numeric_id(&project.reference).to_string();
+
+    let mut score = 0;
+    for token in query_tokens {
+        let name_tokens = || name_forms.iter().flatten();
+        score += if name_tokens().any(|name| name == token) || *token == id {
+            EXACT_NAME_SCORE
+        } else if name_tokens().any(|name| name.starts_with(token.as_str())) {
+            PREFIX_NAME_SCORE
+        } else if details
+            .iter()
+            .any(|detail| detail.starts_with(token.as_str()))
+        {
+            DETAIL_SCORE
+        } else if name_tokens().any(|name| name.contains(token.as_str())) {
+            SUBSTRING_NAME_SCORE
+        } else {
+            return None;
+        };
+    }
+    if !query.is_empty()
+        && name_forms
+            .iter()
+            .any(|form| form.join(" ").starts_with(query))
+    {
+        score += NAME_PREFIX_BONUS;
+    }
+    Some(score)
+}
+
+/// Lowercase words with umlauts transliterated ("Straße Süd" -> ["strasse", "sued"]).
+/// Punctuation separates words.
+fn tokens(value: &str) -> Vec<String> {
+    let mut normalized = String::with_capacity(value.len());
+    for c in value.chars().flat_map(char::to_lowercase) {
+        match c {
+            'ä' => normalized.push_str("ae"),
+            'ö' => normalized.push_str("oe"),
+            'ü' => normalized.push_str("ue"),
+            'ß' => normalized.push_str("ss"),
+            c if c.is_alphanumeric() => normalized.push(c),
+            _ => normalized.push(' '),
+        }
+    }
+    normalized.split_whitespace().map(str::to_string).collect()
+}
+
+// The second spelling users type when they leave out the umlaut: "Muller" for "Müller".
+fn strip_umlauts(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| match c {
+            'ä' => 'a',
+            'ö' => 'o',
+            'ü' => 'u',
+            'Ä' => 'A',
+            'Ö' => 'O',
+            'Ü' => 'U',
+            c => c,
+        })
+        .collect()
+}
+
+fn status_rank(status: &str) -> u8 {
+    match status {
+        "in_progress" => 0,
+        "new_status" => 1,
+        "deferred" => 2,
+        "done" => 3,
+        "abandoned" => 4,
+        "cancelled" => 5,
+        _ => 6,
+    }
+}
+
+fn map_cache_entry_to_summary(project: &DayliteProjectCacheEntry) -> DayliteProjectSummary {
+    DayliteProjectSummary {
+        reference: project.reference.clone(),
+        name: project.name.clone(),
+        status: Some(project.status.clone()),
+        category: project.category.clone(),
+        keywords: project.keywords.clone(),
+        due: project.due.clone(),
+        started: None,
+        completed: None,
+        create_date: None,
+        modify_date: project.modify_date.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn project(id: u32, name: &str, status: &str, modify_date: &str) -> DayliteProjectCacheEntry {
+        DayliteProjectCacheEntry {
+            reference: format!("/v1/projects/{id}"),
+            name: name.to_string(),
+            status: status.to_string(),
+            modify_date: Some(modify_date.to_string()),
+            ..DayliteProjectCacheEntry::default()
+        }
+    }
+
+    fn search(projects: &[DayliteProjectCacheEntry], term: &str) -> Vec<String> {
+        search_cached_projects(
+            projects,
+            &DayliteSearchInput {
+                search_term: term.to_string(),
+                ..DayliteSearchInput::default()
+            },
+        )
+        .unwrap()
+        .results
+        .into_iter()
+        .map(|project| project.name)
+        .collect()
+    }
+
+    #[test]
+    fn normalizes_case_umlauts_and_eszett() {
+        assert_eq!(tokens("Straße SÜD-Ost"), vec!["strasse", "sued", "ost"]);
+
+        let projects = vec![project(
+            1,
+            "Bäckerei Müller Großküche",
+            "new_status",
+            "2026-01-01",
+        )];
+        for term in ["müller", "MUELLER", "muller", "grosskueche", "bäck"] {
+            assert_eq!(search(&projects, term).len(), 1, "{term}");
+        }
+    }
+
+    #[test]
+    fn every_token_must_match_by_prefix_or_substring() {
+        let projects = vec![
+            project(1, "Dachsanierung Rostock", "new_status", "2026-01-01"),
+            project(2, "Fassade Rostock", "new_status", "2026-01-01"),
+        ];
+
+        assert_eq!(search(&projects, "ros dach"), vec!["Dachsanierung Rostock"]);
+        assert_eq!(
+            search(&projects, "sanierung"),
+            vec!["Dachsanierung Rostock"]
+        );
+        assert!(search(&projects, "rostock wismar").is_empty());
+    }
+
+    #[test]
+    fn ranks_by_match_then_status_then_recency() {
+        let projects = vec![
+            project(1, "Nordhalle", "done", "2026-03-01T00:00:00.000Z"),
+            project(
+                2,
+                "Halle Nord alt",
+                "in_progress",
+                "2025-01-01T00:00:00.000Z",
+            ),
+            project(3, "Halle Nord", "in_progress", "2026-02-01T00:00:00.000Z"),
+            project(4, "Lager Nord", "done", "2026-03-01T00:00:00.000Z"),
+            project(5, "Nord", "cancelled", "2024-01-01T00:00:00.000Z"),
+        ];
+
+        assert_eq!(
+            search(&projects, "nord"),
+            vec![
+                "Nord",
+                "Nordhalle",
+                "Halle Nord",
+                "Halle Nord alt",
+                "Lager Nord"
+            ]
+        );
+    }
+
+    #[test]
+    fn matches_categories_keywords_and_project_ids() {
+        let mut wartung = project(42, "Objekt Hafen", "new_status", "2026-01-01");
+        wartung.category = Some("Wartung".to_string());
+        wartung.keywords = vec!["Lüftung".to_string()];
+        let projects = vec![wartung, project(7, "Hafenamt", "new_status", "2026-01-01")];
+
+        assert_eq!(search(&projects, "wartung"), vec!["Objekt Hafen"]);
+        assert_eq!(search(&projects, "luftung"), vec!["Objekt Hafen"]);
+        assert_eq!(search(&projects, "42"), vec!["Objekt Hafen"]);
+    }
+
+    #[test]
+    fn applies_category_keyword_and_due_filters() {
+        let mut wartung = project(1, "Objekt Hafen", "new_status", "2026-01-01");
+        wartung.category = Some("Wartung".to_string());
+        wartung.keywords = vec!["Lüftung".to_string()];
+        wartung.due = Some("2026-03-15T00:00:00.000Z".to_string());
+        let mut later = wartung.clone();
+        later.reference = "/v1/projects/2".to_string();
+        later.name = "Objekt Hafen Süd".to_string();
+        later.due = Some("2026-04-02T00:00:00.000Z".to_string());
+        let mut montage = wartung.clone();
+        montage.reference = "/v1/projects/3".to_string();
+        montage.name = "Objekt Hafen Nord".to_string();
+        montage.category = Some("Montage".to_string());
+
+        let result = search_cached_projects(
+            &[wartung, later, montage],
+            &DayliteSearchInput {
+                search_term: "hafen".to_string(),
+                category: Some("wartung".to_string()),
+                keywords: Some(vec!["LÜFTUNG".to_string()]),
+                due_from: Some("2026-03-01".to_string()),
+                due_to: Some("2026-03-31".to_string()),
+                ..DayliteSearchInput::default()
+            },
+        )
+        .unwrap();
+
+        let names: Vec<&str> = result.results.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Objekt Hafen"]);
+    }
+
+    #[test]
+    fn applies_status_filter_and_limit() {
+        let projects = vec![
+            project(1, "Projekt A", "done", "2026-01-01"),
+            project(2, "Projekt B", "in_progress", "2026-01-02"),
+            project(3, "Projekt C", "in_progress", "2026-01-03"),
+        ];
+
+        let result = search_cached_projects(
+            &projects,
+            &DayliteSearchInput {
+                search_term: "projekt".to_string(),
+                statuses: Some(vec!["in_progress".to_string()]),
+                limit: Some(1),
+                ..DayliteSearchInput::default()
+            },
+        )
+        .unwrap();
+
+        assert!(result.from_cache);
+        assert_eq!(result.results.len(), 1);
+        assert_eq!(result.results[0].name, "Projekt C");
+    }
+
+    #[test]
+    fn only_connection_failures_count_as_offline() {
+        let error = |code, http_status| DayliteApiError::new(code, http_status, "", "");
+
+        assert!(is_offline_error(&error(
+            DayliteApiErrorCode::RequestFailed,
+            None
+        )));
+        assert!(is_offline_error(&error(DayliteApiErrorCode::Timeout, None)));
+        assert!(!is_offline_error(&error(
+            DayliteApiErrorCode::ServerError,
+            Some(503)
+        )));
+        assert!(!is_offline_error(&error(
+            DayliteApiErrorCode::MissingToken,
+            None
+        )));
+    }
+}
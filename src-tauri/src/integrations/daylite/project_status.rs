@@ -0,0 +1,342 @@
+use super::auth_flow::send_authenticated_request;
+use super::client::{DayliteApiClient, DayliteHttpMethod, DayliteHttpRequest};
+use super::project_details::get_optional_json;
+use super::projects::start_of_day;
+use super::shared::{with_token_refresh_lock, DayliteApiError, DayliteTokenState};
+use crate::integrations::local_store::{LocalStore, ProjectStatusRule};
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde_json::json;
+
+// Finished projects are never reopened by planning, even if a rule is configured for them.
+const PROTECTED_STATUSES: [&str; 3] = ["done", "cancelled", "abandoned"];
+// Only statuses Daylite documents are acted on; a missing or unknown one is left alone
+// rather than being read as `new_status`.
+const KNOWN_STATUSES: [&str; 6] = [
+    "new_status",
+    "in_progress",
+    "deferred",
+    "done",
+    "cancelled",
+    "abandoned",
+];
+
+// The raw record, because `PlanningProjectRecord` maps unknown statuses to `new_status`.
+#[derive(Debug, Clone, Deserialize)]
+struct DayliteProjectStatusDto {
+    #[serde(rename = "self")]
+    reference: String,
+    #[serde(default)]
+    status: Option<String>,
+    #[serde(default)]
+    started: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct StatusChange {
+    from: String,
+    to: String,
+    started: Option<String>,
+}
+
+/// Applies the configured status rules to the project an assignment was just created on.
+/// Best effort and detached like the assignment mirror: the calendar write has already
+/// succeeded, so failures are logged, never returned.
+pub(crate) fn spawn_assignment_status_rules(
+    store: &LocalStore,
+    project_ref: String,
+    day: NaiveDate,
+) {
+    let automation = &store.project_status_automation;
+    if !automation.enabled || automation.rules.is_empty() {
+        return;
+    }
+    let store = store.clone();
+    tauri::async_runtime::spawn(async move {
+        apply_assignment_status_rules(&store, &project_ref, day).await;
+    });
+}
+
+async fn apply_assignment_status_rules(store: &LocalStore, project_ref: &str, day: NaiveDate) {
+    let automation = &store.project_status_automation;
+
+    let client = match DayliteApiClient::new(&store.api_endpoints.daylite_base_url) {
+        Ok(client) => client,
+        Err(e) => {
+            eprintln!("daylite: status rules skipped: {}", e.technical_message);
+            return;
+        }
+    };
+    let result = with_token_refresh_lock(|tokens| {
+        apply_status_rules_core(&client, tokens, &automation.rules, project_ref, day)
+    })
+    .await;
+    match result {
+        Ok(Some(change)) => eprintln!(
+            "daylite: project {project_ref} status {} -> {}{}",
+            change.from,
+            change.to,
+            change
+                .started
+                .map(|started| format!(", started {started}"))
+                .unwrap_or_default()
+        ),
+        Ok(None) => {}
+        Err(e) => eprintln!(
+            "daylite: status rules for {project_ref} failed: {}",
+            e.technical_message
+        ),
+    }
+}
+
+async fn apply_status_rules_core(
+    client: &DayliteApiClient,
+    token_state: DayliteTokenState,
+    rules: &[ProjectStatusRule],
+    project_ref: &str,
+    day: NaiveDate,
+) -> Result<(Option<StatusChange>, DayliteTokenState), DayliteApiError> {
+    let (project, token_state) =
+        get_optional_json::<DayliteProjectStatusDto>(client, token_state, project_ref).await?;
+    let Some(project) = project else {
+        return Ok((None, token_state));
+    };
+    let status = project
+        .status
+        .as_deref()
+        .map(|status| status.trim().to_lowercase())
+        .unwrap_or_default();
+    let Some(rule) = matching_rule(rules, &status) else {
+        return Ok((None, token_state));
+    };
+
+    let has_started = project
+        .started
+        .as_deref()
+        .is_some_and(|started| !started.trim().is_empty());
+    let change = StatusChange {
+        from: status,
+        to: rule.to_status.trim().to_string(),
+        started: (rule.set_started && !has_started).then(|| start_of_day(day)),
+    };
+    let mut body = json!({ "status": change.to });
+    if let Some(started) = &change.started {
+        body["started"] = json!(started);
+    }
+
+    let reference = project.reference.trim();
+    let path = reference.strip_prefix("/v1").unwrap_or(reference);
+    let token_state = send_authenticated_request(
+        client,
+        token_state,
+        DayliteHttpRequest {
+            body: Some(body),
+            ..DayliteHttpRequest::new(DayliteHttpMethod::Patch, path)
+        },
+    )
+    .await?;
+    Ok((Some(change), token_state))
+}
+
+fn matching_rule<'a>(
+    rules: &'a [ProjectStatusRule],
+    status: &str,
+) -> Option<&'a ProjectStatusRule> {
+    if !KNOWN_STATUSES.contains(&status) || PROTECTED_STATUSES.contains(&status) {
+        return None;
+    }
+    rules.iter().find(|rule| {
+        let to = rule.to_status.trim();
+        rule.from_status.trim().eq_ignore_ascii_case(status)
+            && !to.is_empty()
+            && !to.eq_ignore_ascii_case(status)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::integrations::daylite::test_support::{
+        mock_response, valid_token_state, MockTransport,
+    };
+    use crate::integrations::local_store::ProjectStatusAutomation;
+
+    fn day() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 3, 2).unwrap()
+    }
+
+    #[test]
+    fn new_project_moves_to_in_progress_and_gets_started_date() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(
+                    200,
+                    r#"{"self":"/v1/projects/3001","name":"Hafen Nord","status":"new_status"}"#,
+                )),
+                Ok(mock_response(200, "{}")),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let rules = ProjectStatusAutomation::default().rules;
+
+            let (change, _) = apply_status_rules_core(
+                &client,
+                valid_token_state(),
+                &rules,
+                "/v1/projects/3001",
+                day(),
+            )
+            .await
+            .expect("rules should apply");
+
+            assert_eq!(
+                change,
+                Some(StatusChange {
+                    from: "new_status".to_string(),
+                    to: "in_progress".to_string(),
+                    started: Some("2026-03-02T00:00:00.000Z".to_string()),
+                })
+            );
+            let requests = transport.requests();
+            assert_eq!(requests[1].method, DayliteHttpMethod::Patch);
+            assert_eq!(requests[1].path, "/projects/3001");
+            assert_eq!(
+                requests[1].body,
+                Some(json!({ "status": "in_progress", "started": "2026-03-02T00:00:00.000Z" }))
+            );
+        });
+    }
+
+    #[test]
+    fn existing_started_date_is_kept() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(
+                    200,
+                    r#"{"self":"/v1/projects/3001","name":"Hafen Nord","status":"new_status","started":"2026-01-15T00:00:00Z"}"#,
+                )),
+                Ok(mock_response(200, "{}")),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let rules = ProjectStatusAutomation::default().rules;
+
+            apply_status_rules_core(
+                &client,
+                valid_token_state(),
+                &rules,
+                "/v1/projects/3001",
+                day(),
+            )
+            .await
+            .expect("rules should apply");
+
+            assert_eq!(
+                transport.requests()[1].body,
+                Some(json!({ "status": "in_progress" }))
+            );
+        });
+    }
+
+    #[test]
+    fn done_project_is_never_changed() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![Ok(mock_response(
+                200,
+                r#"{"self":"/v1/projects/3001","name":"Hafen Nord","status":"done"}"#,
+            ))]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let rules = vec![ProjectStatusRule {
+                from_status: "done".to_string(),
+                to_status: "in_progress".to_string(),
+                set_started: false,
+            }];
+
+            let (change, _) = apply_status_rules_core(
+                &client,
+                valid_token_state(),
+                &rules,
+                "/v1/projects/3001",
+                day(),
+            )
+            .await
+            .expect("rules should apply");
+
+            assert_eq!(change, None);
+            assert_eq!(transport.requests().len(), 1);
+        });
+    }
+
+    #[test]
+    fn missing_or_unknown_status_is_never_changed() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(
+                    200,
+                    r#"{"self":"/v1/projects/1","name":"Ohne Status"}"#,
+                )),
+                Ok(mock_response(
+                    200,
+                    r#"{"self":"/v1/projects/2","name":"Neu","status":"on_hold"}"#,
+                )),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+            let rules = ProjectStatusAutomation::default().rules;
+
+            for project_ref in ["/v1/projects/1", "/v1/projects/2"] {
+                let (change, _) = apply_status_rules_core(
+                    &client,
+                    valid_token_state(),
+                    &rules,
+                    project_ref,
+                    day(),
+                )
+                .await
+                .expect("rules should apply");
+                assert_eq!(change, None);
+            }
+            assert_eq!(transport.requests().len(), 2);
+        });
+    }
+
+    #[test]
+    fn abandoned_and_cancelled_projects_are_protected() {
+        let rules = vec![
+            ProjectStatusRule {
+                from_status: "abandoned".to_string(),
+                to_status: "in_progress".to_string(),
+                set_started: false,
+            },
+            ProjectStatusRule {
+                from_status: "cancelled".to_string(),
+                to_status: "in_progress".to_string(),
+                set_started: false,
+            },
+        ];
+
+        assert!(matching_rule(&rules, "abandoned").is_none());
+        assert!(matching_rule(&rules, "cancelled").is_none());
+        assert!(!ProjectStatusAutomation::default().enabled);
+    }
+
+    #[test]
+    fn only_matching_rules_with_a_new_status_apply() {
+        let rules = vec![
+            ProjectStatusRule {
+                from_status: "deferred".to_string(),
+                to_status: " ".to_string(),
+                set_started: false,
+            },
+            ProjectStatusRule {
+                from_status: " New_Status ".to_string(),
+                to_status: "in_progress".to_string(),
+                set_started: false,
+            },
+        ];
+
+        assert!(matching_rule(&rules, "in_progress").is_none());
+        assert!(matching_rule(&rules, "deferred").is_none());
+        assert_eq!(
+            matching_rule(&rules, "new_status").map(|rule| rule.to_status.as_str()),
+            Some("in_progress")
+        );
+    }
+}
@@ -2,17 +2,22 @@ use super::auth_flow::send_authenticated_json;
 use super::client::DayliteApiClient;
 use super::client::DayliteHttpMethod;
 use super::client::DayliteHttpRequest;
+use super::pagination::search_all_pages;
+use super::project_details::get_optional_json;
+use super::project_search::{is_offline_error, search_cached_projects};
 use super::shared::{
-    build_limit_query, run_daylite_command, with_token_refresh_lock, DayliteApiError,
-    DayliteSearchInput, DayliteSearchResult, DayliteSearchSort, DayliteTokenState,
+    build_limit_query, load_store_or_error, run_daylite_command, with_token_refresh_lock,
+    DayliteApiError, DayliteApiErrorCode, DayliteSearchInput, DayliteSearchResult,
+    DayliteSearchSort, DayliteTokenState,
 };
 use chrono::{DateTime, NaiveDate, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use specta::Type;
+use std::collections::HashMap;
 
 #[derive(Debug, Clone, Deserialize)]
-struct DayliteProjectSummaryDto {
+pub(super) struct DayliteProjectSummaryDto {
     #[serde(rename = "self")]
     reference: String,
     #[serde(default)]
@@ -130,33 +135,51 @@ pub async fn daylite_search_projects(
     app: tauri::AppHandle,
     input: DayliteSearchInput,
 ) -> Result<DayliteSearchResult<DayliteProjectSummary>, DayliteApiError> {
-    run_daylite_command(app, |client, tokens| async move {
-        search_projects_core(&client, tokens, &input).await
+    let result = run_daylite_command(app.clone(), |client, tokens| {
+        let input = input.clone();
+        async move { search_projects_core(&client, tokens, &input).await }
     })
-    .await
+    .await;
+
+    match result {
+        // Without a connection the cached projects are better than an error in the modal.
+        Err(error) if is_offline_error(&error) => {
+            eprintln!(
+                "daylite: project search falls back to the cache: {}",
+                error.technical_message
+            );
+            let store = load_store_or_error(app)?;
+            search_cached_projects(&store.daylite_cache.projects, &input)
+        }
+        result => result,
+    }
 }
 
 pub(super) async fn list_projects_core(
     client: &DayliteApiClient,
     token_state: DayliteTokenState,
 ) -> Result<(Vec<PlanningProjectRecord>, DayliteTokenState), DayliteApiError> {
-    let (search_result, token_state) =
-        send_authenticated_json::<DayliteSearchResult<DayliteProjectSummaryDto>>(
-            client,
-            token_state,
-            DayliteHttpRequest {
-                query: vec![("full-records".to_string(), "true".to_string())],
-                body: Some(json!({})),
-                ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/projects/_search")
-            },
-        )
-        .await?;
-
-    let projects = search_result
-        .results
-        .into_iter()
-        .map(map_daylite_project_summary)
-        .collect();
+    let (pages, token_state) = search_all_pages::<DayliteProjectSummaryDto>(
+        client,
+        token_state,
+        DayliteHttpRequest {
+            query: vec![("full-records".to_string(), "true".to_string())],
+            body: Some(json!({})),
+            ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/projects/_search")
+        },
+        |_| false,
+    )
+    .await?;
+
+    let mut projects: Vec<PlanningProjectRecord> = dedup_by_reference(
+        pages
+            .results
+            .into_iter()
+            .map(map_daylite_project_summary)
+            .collect(),
+        |project| &project.reference,
+    );
+    projects.sort_by_key(|project| extract_numeric_id(&project.reference));
 
     Ok((projects, token_state))
 }
@@ -209,21 +232,7 @@ pub(super) async fn search_projects_core(
     ),
     DayliteApiError,
 > {
-    let body = match &input.statuses {
-        Some(statuses) if !statuses.is_empty() => {
-            let clauses: Vec<serde_json::Value> = statuses
-                .iter()
-                .map(|s| {
-                    json!({
-                        "name": { "contains": input.search_term },
-                        "status": { "equal": s }
-                    })
-                })
-                .collect();
-            json!(clauses)
-        }
-        _ => json!({ "name": { "contains": input.search_term } }),
-    };
+    let body = build_project_search_body(input)?;
 
     let mut query = build_limit_query(input.limit);
     if input.full_records == Some(true) {
@@ -233,43 +242,181 @@ pub(super) async fn search_projects_core(
         query.push(("start".to_string(), start.clone()));
     }
 
-    let (search_result, token_state) =
-        send_authenticated_json::<DayliteSearchResult<DayliteProjectSummaryDto>>(
-            client,
-            token_state,
-            DayliteHttpRequest {
-                query,
-                body: Some(body),
-                ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/projects/_search")
-            },
-        )
-        .await?;
-
-    let mut results: Vec<DayliteProjectSummary> = search_result
-        .results
-        .into_iter()
-        .map(normalize_project_summary)
-        .collect();
+    // Daylite applies a limit per page, so a limited search is a single page and its `next`
+    // link is handed back for the caller to continue from. Without a limit every page is
+    // loaded.
+    let limited = input.limit.is_some();
+    let (pages, token_state) = search_all_pages::<DayliteProjectSummaryDto>(
+        client,
+        token_state,
+        DayliteHttpRequest {
+            query,
+            body: Some(body),
+            ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/projects/_search")
+        },
+        |_| limited,
+    )
+    .await?;
+
+    let mut results: Vec<DayliteProjectSummary> = dedup_by_reference(
+        pages
+            .results
+            .into_iter()
+            .map(normalize_project_summary)
+            .collect(),
+        |project| &project.reference,
+    );
 
     match input.sort {
         Some(DayliteSearchSort::Name) => results.sort_by(|a, b| a.name.cmp(&b.name)),
         _ => results.sort_by_key(|p| extract_numeric_id(&p.reference)),
     }
 
-    if let Some(limit) = input.limit {
+    // A page longer than the limit is only cut when nothing follows it; with a `next` link
+    // the cut records could never be reached again.
+    if let Some(limit) = input.limit.filter(|_| pages.next.is_none()) {
         results.truncate(limit as usize);
     }
 
     Ok((
         DayliteSearchResult {
             results,
-            next: normalize_optional_string(search_result.next),
+            next: normalize_optional_string(pages.next),
+            from_cache: false,
         },
         token_state,
     ))
 }
 
-fn extract_numeric_id(reference: &str) -> u64 {
+/// The `_search` body for `input`: all filters in one AND-object, repeated once per status
+/// as OR clauses when statuses are given.
+fn build_project_search_body(
+    input: &DayliteSearchInput,
+) -> Result<serde_json::Value, DayliteApiError> {
+    let mut filter = serde_json::Map::new();
+    filter.insert("name".to_string(), json!({ "contains": input.search_term }));
+    if let Some(category) = normalize_optional_string(input.category.clone()) {
+        filter.insert("category".to_string(), json!({ "equal": category }));
+    }
+    let keywords = normalize_keywords(input.keywords.clone().unwrap_or_default());
+    if !keywords.is_empty() {
+        filter.insert("keywords".to_string(), json!({ "any": keywords }));
+    }
+    if let Some(due) = DateRange::parse(&input.due_from, &input.due_to)?.to_filter() {
+        filter.insert("due".to_string(), due);
+    }
+    if let Some(modified) = DateRange::parse(&input.modified_from, &input.modified_to)?.to_filter()
+    {
+        filter.insert("modify_date".to_string(), modified);
+    }
+    if let Some(company) = normalize_optional_string(input.company_reference.clone()) {
+        filter.insert(
+            "companies".to_string(),
+            json!({ "any": { "company": { "equal": company } } }),
+        );
+    }
+
+    Ok(match &input.statuses {
+        Some(statuses) if !statuses.is_empty() => json!(statuses
+            .iter()
+            .map(|status| {
+                let mut clause = filter.clone();
+                clause.insert("status".to_string(), json!({ "equal": status }));
+                serde_json::Value::Object(clause)
+            })
+            .collect::<Vec<_>>()),
+        _ => serde_json::Value::Object(filter),
+    })
+}
+
+/// An inclusive range of calendar days from a search input; either side may be open.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub(super) struct DateRange {
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+impl DateRange {
+    pub(super) fn parse(
+        from: &Option<String>,
+        to: &Option<String>,
+    ) -> Result<Self, DayliteApiError> {
+        let range = Self {
+            from: parse_filter_date(from)?,
+            to: parse_filter_date(to)?,
+        };
+        if let (Some(from), Some(to)) = (range.from, range.to) {
+            if from > to {
+                return Err(DayliteApiError::new(
+                    DayliteApiErrorCode::InvalidConfiguration,
+                    None,
+                    "Das Startdatum des Filters liegt nach dem Enddatum.",
+                    format!("Ungültiger Datumsbereich {from} bis {to}"),
+                ));
+            }
+        }
+        Ok(range)
+    }
+
+    // Daylite compares timestamps, so the inclusive end day becomes "before the next day".
+    fn to_filter(self) -> Option<serde_json::Value> {
+        let mut filter = serde_json::Map::new();
+        if let Some(from) = self.from {
+            filter.insert("greater_than_equal".to_string(), json!(start_of_day(from)));
+        }
+        if let Some(to) = self.to.and_then(|to| to.succ_opt()) {
+            filter.insert("less_than".to_string(), json!(start_of_day(to)));
+        }
+        (!filter.is_empty()).then_some(serde_json::Value::Object(filter))
+    }
+
+    /// Whether a normalized timestamp such as "2026-02-15T00:00:00.000Z" falls on a day in
+    /// the range. Values without a date only match an open range.
+    pub(super) fn contains(&self, timestamp: Option<&str>) -> bool {
+        if self.from.is_none() && self.to.is_none() {
+            return true;
+        }
+        let Some(day) = timestamp
+            .and_then(|value| value.get(..10))
+            .and_then(|value| NaiveDate::parse_from_str(value, "%Y-%m-%d").ok())
+        else {
+            return false;
+        };
+        self.from.is_none_or(|from| day >= from) && self.to.is_none_or(|to| day <= to)
+    }
+}
+
+fn parse_filter_date(value: &Option<String>) -> Result<Option<NaiveDate>, DayliteApiError> {
+    let Some(raw_value) = normalize_optional_string(value.clone()) else {
+        return Ok(None);
+    };
+    NaiveDate::parse_from_str(&raw_value, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|error| {
+            DayliteApiError::new(
+                DayliteApiErrorCode::InvalidConfiguration,
+                None,
+                format!("Das Datum `{raw_value}` ist ungültig (erwartet JJJJ-MM-TT)."),
+                format!("Ungültiges Filterdatum `{raw_value}`: {error}"),
+            )
+        })
+}
+
+pub(super) fn start_of_day(date: NaiveDate) -> String {
+    format!("{}T00:00:00.000Z", date.format("%Y-%m-%d"))
+}
+
+/// Keeps the first record per reference; pages can overlap when records change while
+/// Daylite is paged through.
+pub(super) fn dedup_by_reference<T>(records: Vec<T>, reference: impl Fn(&T) -> &str) -> Vec<T> {
+    let mut seen = std::collections::HashSet::new();
+    records
+        .into_iter()
+        .filter(|record| seen.insert(reference(record).to_string()))
+        .collect()
+}
+
+pub(super) fn extract_numeric_id(reference: &str) -> u64 {
     reference
         .rsplit('/')
         .next()
@@ -277,7 +424,9 @@ fn extract_numeric_id(reference: &str) -> u64 {
         .unwrap_or(u64::MAX)
 }
 
-fn map_daylite_project_summary(project: DayliteProjectSummaryDto) -> PlanningProjectRecord {
+pub(super) fn map_daylite_project_summary(
+    project: DayliteProjectSummaryDto,
+) -> PlanningProjectRecord {
     let project = normalize_project_summary(project);
 
     PlanningProjectRecord {
@@ -313,7 +462,7 @@ fn normalize_required_string(value: String) -> String {
     value.trim().to_string()
 }
 
-fn normalize_optional_string(value: Option<String>) -> Option<String> {
+pub(super) fn normalize_optional_string(value: Option<String>) -> Option<String> {
     value.and_then(|candidate| {
         let normalized = candidate.trim();
         if normalized.is_empty() {
@@ -338,7 +487,7 @@ fn normalize_keywords(values: Vec<String>) -> Vec<String> {
         .collect()
 }
 
-fn normalize_optional_date(value: Option<String>) -> Option<String> {
+pub(super) fn normalize_optional_date(value: Option<String>) -> Option<String> {
     let raw_value = normalize_optional_string(value)?;
 
     if let Ok(parsed_date_time) = DateTime::parse_from_rfc3339(&raw_value) {
@@ -414,7 +563,37 @@ pub(crate) async fn fetch_project_by_reference(
     .ok()
 }
 
-fn project_status_to_string(status: &PlanningProjectStatus) -> &'static str {
+/// Resolves several project references under a single token lock. A project that
+/// no longer exists in Daylite (HTTP 404) maps to `None` instead of failing the batch.
+pub(crate) async fn fetch_project_records(
+    app: tauri::AppHandle,
+    project_refs: &[String],
+) -> Result<HashMap<String, Option<PlanningProjectRecord>>, DayliteApiError> {
+    run_daylite_command(app, |client, tokens| async move {
+        let mut token_state = tokens;
+        let mut records = HashMap::new();
+        for project_ref in project_refs {
+            let (record, updated_tokens) =
+                get_project_core(&client, token_state, project_ref).await?;
+            token_state = updated_tokens;
+            records.insert(project_ref.clone(), record);
+        }
+        Ok((records, token_state))
+    })
+    .await
+}
+
+pub(super) async fn get_project_core(
+    client: &DayliteApiClient,
+    token_state: DayliteTokenState,
+    project_ref: &str,
+) -> Result<(Option<PlanningProjectRecord>, DayliteTokenState), DayliteApiError> {
+    let (summary, token_state) =
+        get_optional_json::<DayliteProjectSummaryDto>(client, token_state, project_ref).await?;
+    Ok((summary.map(map_daylite_project_summary), token_state))
+}
+
+pub(crate) fn project_status_to_string(status: &PlanningProjectStatus) -> &'static str {
     match status {
         PlanningProjectStatus::InProgress => "in_progress",
         PlanningProjectStatus::Done => "done",
@@ -428,9 +607,9 @@ fn project_status_to_string(status: &PlanningProjectStatus) -> &'static str {
 #[cfg(test)]
 mod tests {
     use super::{
-        list_projects_core, map_daylite_project_summary, map_project_status,
-        query_overdue_projects_core, search_projects_core, DayliteProjectSummaryDto,
-        PlanningProjectStatus,
+        build_project_search_body, get_project_core, list_projects_core,
+        map_daylite_project_summary, map_project_status, query_overdue_projects_core,
+        search_projects_core, DateRange, DayliteProjectSummaryDto, PlanningProjectStatus,
     };
     use crate::integrations::daylite::client::DayliteApiClient;
     use crate::integrations::daylite::client::DayliteHttpMethod;
@@ -517,6 +696,188 @@ mod tests {
         });
     }
 
+    #[test]
+    fn list_projects_merges_pages_without_duplicates() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/projects/12","name":"Projekt C"},{"self":"/v1/projects/3","name":"Projekt A"}],"next":"/v1/projects/_search?full-records=true&start=2"}"#,
+                )),
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/projects/3","name":"Projekt A (neu)"},{"self":"/v1/projects/7","name":"Projekt B"}],"next":null}"#,
+                )),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+
+            let (projects, _) = list_projects_core(&client, valid_token_state())
+                .await
+                .expect("list should succeed");
+
+            let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+            assert_eq!(names, vec!["Projekt A", "Projekt B", "Projekt C"]);
+            let requests = transport.requests();
+            assert_eq!(requests.len(), 2);
+            assert_eq!(requests[1].path, "/projects/_search");
+            assert_eq!(
+                requests[1].query,
+                vec![
+                    ("full-records".to_string(), "true".to_string()),
+                    ("start".to_string(), "2".to_string()),
+                ]
+            );
+            assert_eq!(requests[1].body, Some(serde_json::json!({})));
+        });
+    }
+
+    #[test]
+    fn search_projects_without_limit_loads_every_page() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/projects/4","name":"Nord 4"}],"next":"/v1/projects/_search?start=1"}"#,
+                )),
+                Ok(mock_response(
+                    200,
+                    r#"{"results":[{"self":"/v1/projects/2","name":"Nord 2"},{"self":"/v1/projects/9","name":"Nord 9"}],"next":null}"#,
+                )),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+
+            let (result, _) = search_projects_core(
+                &client,
+                valid_token_state(),
+                &DayliteSearchInput {
+                    search_term: "Nord".to_string(),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("search should succeed");
+
+            let references: Vec<&str> = result
+                .results
+                .iter()
+                .map(|p| p.reference.as_str())
+                .collect();
+            assert_eq!(
+                references,
+                vec!["/v1/projects/2", "/v1/projects/4", "/v1/projects/9"]
+            );
+            assert_eq!(result.next, None);
+            assert_eq!(transport.requests().len(), 2);
+        });
+    }
+
+    #[test]
+    fn limited_search_keeps_the_whole_page_and_hands_back_next() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![Ok(mock_response(
+                200,
+                r#"{"results":[{"self":"/v1/projects/9","name":"Nord 9"},{"self":"/v1/projects/2","name":"Nord 2"}],"next":"/v1/projects/_search?limit=2&start=2"}"#,
+            ))]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+
+            let (result, _) = search_projects_core(
+                &client,
+                valid_token_state(),
+                &DayliteSearchInput {
+                    search_term: "Nord".to_string(),
+                    limit: Some(2),
+                    ..Default::default()
+                },
+            )
+            .await
+            .expect("search should succeed");
+
+            let references: Vec<&str> = result
+                .results
+                .iter()
+                .map(|p| p.reference.as_str())
+                .collect();
+            assert_eq!(references, vec!["/v1/projects/2", "/v1/projects/9"]);
+            assert_eq!(
+                result.next,
+                Some("/v1/projects/_search?limit=2&start=2".to_string())
+            );
+            assert_eq!(transport.requests().len(), 1);
+        });
+    }
+
+    #[test]
+    fn search_filters_are_combined_into_each_status_clause() {
+        let body = build_project_search_body(&DayliteSearchInput {
+            search_term: "Hafen".to_string(),
+            statuses: Some(vec!["new_status".to_string(), "in_progress".to_string()]),
+            category: Some(" Wartung ".to_string()),
+            keywords: Some(vec!["Lüftung".to_string(), " ".to_string()]),
+            due_from: Some("2026-03-01".to_string()),
+            due_to: Some("2026-03-31".to_string()),
+            modified_from: Some("2026-01-01".to_string()),
+            company_reference: Some("/v1/companies/12".to_string()),
+            ..Default::default()
+        })
+        .expect("filters should be valid");
+
+        let clause = serde_json::json!({
+            "name": { "contains": "Hafen" },
+            "category": { "equal": "Wartung" },
+            "keywords": { "any": ["Lüftung"] },
+            "due": {
+                "greater_than_equal": "2026-03-01T00:00:00.000Z",
+                "less_than": "2026-04-01T00:00:00.000Z"
+            },
+            "modify_date": { "greater_than_equal": "2026-01-01T00:00:00.000Z" },
+            "companies": { "any": { "company": { "equal": "/v1/companies/12" } } }
+        });
+        let mut new_clause = clause.clone();
+        new_clause["status"] = serde_json::json!({ "equal": "new_status" });
+        let mut in_progress_clause = clause;
+        in_progress_clause["status"] = serde_json::json!({ "equal": "in_progress" });
+        assert_eq!(body, serde_json::json!([new_clause, in_progress_clause]));
+    }
+
+    #[test]
+    fn search_without_filters_keeps_the_plain_name_body() {
+        let body = build_project_search_body(&DayliteSearchInput {
+            search_term: "Nord".to_string(),
+            category: Some(" ".to_string()),
+            keywords: Some(vec![]),
+            ..Default::default()
+        })
+        .unwrap();
+
+        assert_eq!(body, serde_json::json!({ "name": { "contains": "Nord" } }));
+    }
+
+    #[test]
+    fn invalid_or_reversed_filter_dates_are_rejected() {
+        let error = build_project_search_body(&DayliteSearchInput {
+            due_from: Some("31.03.2026".to_string()),
+            ..Default::default()
+        })
+        .expect_err("German date format should be rejected");
+        assert_eq!(error.code, DayliteApiErrorCode::InvalidConfiguration);
+
+        assert!(DateRange::parse(
+            &Some("2026-04-01".to_string()),
+            &Some("2026-03-01".to_string())
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn date_range_matches_whole_days() {
+        let range = DateRange::parse(&None, &Some("2026-03-31".to_string())).unwrap();
+
+        assert!(range.contains(Some("2026-03-31T00:00:00.000Z")));
+        assert!(!range.contains(Some("2026-04-01T00:00:00.000Z")));
+        assert!(!range.contains(None));
+        assert!(DateRange::default().contains(None));
+    }
+
     #[test]
     fn search_projects_sends_correct_body_and_query() {
         tauri::async_runtime::block_on(async {
@@ -814,6 +1175,76 @@ mod tests {
         });
     }
 
+    #[test]
+    fn get_project_strips_version_prefix_and_maps_record() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![Ok(mock_response(
+                200,
+                r#"{"self":"/v1/projects/3001","name":" Projekt Nord ","status":"in_progress","due":"2026-06-30"}"#,
+            ))]);
+            let client = DayliteApiClient::with_transport(Box::new(transport.clone()));
+
+            let (record, token_state) =
+                get_project_core(&client, valid_token_state(), "/v1/projects/3001")
+                    .await
+                    .expect("get should succeed");
+
+            let record = record.expect("project should exist");
+            assert_eq!(record.name, "Projekt Nord");
+            assert_eq!(record.status, PlanningProjectStatus::InProgress);
+            assert_eq!(record.due, Some("2026-06-30T00:00:00.000Z".to_string()));
+            assert_eq!(token_state.access_token, "at");
+
+            let requests = transport.requests();
+            assert_eq!(requests.len(), 1);
+            assert_eq!(requests[0].method, DayliteHttpMethod::Get);
+            assert_eq!(requests[0].path, "/projects/3001");
+        });
+    }
+
+    #[test]
+    fn get_project_maps_not_found_to_none_and_keeps_token_state() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(
+                    200,
+                    r#"{"access_token":"new-at","refresh_token":"new-rt","expires_in":3600}"#,
+                )),
+                Ok(mock_response(404, r#"{"error":"not found"}"#)),
+            ]);
+            let client = DayliteApiClient::with_transport(Box::new(transport));
+
+            let (record, token_state) = get_project_core(
+                &client,
+                DayliteTokenState {
+                    access_token: String::new(),
+                    refresh_token: "old-rt".to_string(),
+                    access_token_expires_at_ms: None,
+                },
+                "/v1/projects/9999",
+            )
+            .await
+            .expect("404 should not be an error");
+
+            assert_eq!(record, None);
+            assert_eq!(token_state.refresh_token, "new-rt");
+        });
+    }
+
+    #[test]
+    fn get_project_propagates_server_errors() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![Ok(mock_response(500, "{}"))]);
+            let client = DayliteApiClient::with_transport(Box::new(transport));
+
+            let error = get_project_core(&client, valid_token_state(), "/v1/projects/1")
+                .await
+                .expect_err("500 should fail");
+
+            assert_eq!(error.code, DayliteApiErrorCode::ServerError);
+        });
+    }
+
     #[test]
     fn extract_numeric_id_handles_standard_reference() {
         assert_eq!(super::extract_numeric_id("/v1/projects/3001"), 3001);
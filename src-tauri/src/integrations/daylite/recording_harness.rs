@@ -1,12 +1,14 @@
 use super::auth_flow::refresh_tokens;
-use super::client::DayliteApiClient;
+use super::client::{DayliteApiClient, DayliteHttpMethod, DayliteHttpRequest};
 use super::contacts::{
     list_contacts_core, update_contact_ical_urls_core, DayliteUpdateContactIcalUrlsInput,
 };
+use super::pagination::search_all_pages;
+use super::project_details::get_project_details_core;
 use super::projects::{list_projects_core, query_overdue_projects_core, search_projects_core};
 use super::shared::{DayliteSearchInput, DayliteSearchSort, DayliteTokenState};
 use crate::integrations::http_record_replay::VcrMode;
-use crate::integrations::local_store::LocalStore;
+use crate::integrations::local_store::{LocalStore, PlanningContactSettings};
 use std::sync::{Mutex, OnceLock};
 
 const DAYLITE_BASE_URL_ENV: &str = "DAYLITE_BASE_URL";
@@ -19,6 +21,21 @@ const DAYLITE_VCR_CONTACT_REFERENCE_ENV: &str = "DAYLITE_VCR_CONTACT_REFERENCE";
 const DAYLITE_VCR_PRIMARY_ICAL_URL_ENV: &str = "DAYLITE_VCR_PRIMARY_ICAL_URL";
 const DAYLITE_VCR_ABSENCE_ICAL_URL_ENV: &str = "DAYLITE_VCR_ABSENCE_ICAL_URL";
 
+pub(super) const PROJECT_DETAILS_CASSETTE: &str = "daylite-project-details.json";
+pub(super) const PAGED_SEARCH_CASSETTE: &str = "daylite-search-projects-paged.json";
+// Small pages make any real database span several of them; paging stops after three.
+const PAGED_SEARCH_PAGE_SIZE: usize = 2;
+pub(super) const PAGED_SEARCH_MAX_RESULTS: usize = 3 * PAGED_SEARCH_PAGE_SIZE;
+
+/// The request the multi-page cassette is recorded with and replayed against.
+pub(super) fn paged_search_request() -> DayliteHttpRequest {
+    DayliteHttpRequest {
+        query: vec![("limit".to_string(), PAGED_SEARCH_PAGE_SIZE.to_string())],
+        body: Some(serde_json::json!({})),
+        ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/projects/_search")
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum DayliteVcrScope {
     ReadOnly,
@@ -101,7 +118,7 @@ fn record_daylite_cassettes_from_live_api() {
             access_token_expires_at_ms: Some(u64::MAX),
         };
 
-        list_projects_core(
+        let (projects, _) = list_projects_core(
             &DayliteApiClient::with_env_cassette(&config.base_url, "daylite-list-projects.json")
                 .expect("project list cassette client should be created"),
             stable_token_state.clone(),
@@ -109,6 +126,18 @@ fn record_daylite_cassettes_from_live_api() {
         .await
         .expect("project list cassette should be recorded");
 
+        if let Some(project) = projects.first() {
+            get_project_details_core(
+                &DayliteApiClient::with_env_cassette(&config.base_url, PROJECT_DETAILS_CASSETTE)
+                    .expect("project details cassette client should be created"),
+                stable_token_state.clone(),
+                &project.reference,
+                &PlanningContactSettings::default(),
+            )
+            .await
+            .expect("project details cassette should be recorded");
+        }
+
         search_projects_core(
             &DayliteApiClient::with_env_cassette(&config.base_url, "daylite-search-projects.json")
                 .expect("project search cassette client should be created"),
@@ -120,6 +149,7 @@ fn record_daylite_cassettes_from_live_api() {
                 full_records: None,
                 start: None,
                 sort: None,
+                ..DayliteSearchInput::default()
             },
         )
         .await
@@ -136,6 +166,7 @@ fn record_daylite_cassettes_from_live_api() {
                 full_records: Some(true),
                 start: None,
                 sort: None,
+                ..DayliteSearchInput::default()
             },
         )
         .await
@@ -155,6 +186,7 @@ fn record_daylite_cassettes_from_live_api() {
                 full_records: None,
                 start: None,
                 sort: Some(DayliteSearchSort::Name),
+                ..DayliteSearchInput::default()
             },
         )
         .await
@@ -168,10 +200,21 @@ fn record_daylite_cassettes_from_live_api() {
         .await
         .expect("overdue project cassette should be recorded");
 
+        search_all_pages::<serde_json::Value>(
+            &DayliteApiClient::with_env_cassette(&config.base_url, PAGED_SEARCH_CASSETTE)
+                .expect("paged search cassette client should be created"),
+            stable_token_state.clone(),
+            paged_search_request(),
+            |loaded| loaded >= PAGED_SEARCH_MAX_RESULTS,
+        )
+        .await
+        .expect("paged search cassette should be recorded");
+
         list_contacts_core(
             &DayliteApiClient::with_env_cassette(&config.base_url, "daylite-list-contacts.json")
                 .expect("contact list cassette client should be created"),
             stable_token_state.clone(),
+            &PlanningContactSettings::default(),
         )
         .await
         .expect("contact list cassette should be recorded");
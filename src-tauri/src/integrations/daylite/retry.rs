@@ -0,0 +1,327 @@
+use super::client::BoxFuture;
+use super::shared::{DayliteApiError, DayliteApiErrorCode};
+use chrono::{DateTime, Utc};
+use std::time::Duration;
+
+// Requests in flight across all clients; Daylite throttles per account, so parallel
+// commands and background refreshes share one budget.
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+
+pub(super) type SleepFn = dyn Fn(Duration) -> BoxFuture<'static, ()> + Send + Sync;
+// Returns a value in [0, 1).
+pub(super) type JitterFn = dyn Fn() -> f64 + Send + Sync;
+
+/// How often and how long to wait before repeating a request that hit a rate limit
+/// (429), a server error (5xx) or a timeout. Reads, `_search` POSTs and PATCHes that set
+/// absolute values are safe to repeat; creates are sent with `retryable: false` and are
+/// never repeated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) struct RetryPolicy {
+    pub max_retries: u32,
+    pub base_delay: Duration,
+    pub max_delay: Duration,
+    // A longer Retry-After is not waited out; the error goes to the user instead.
+    pub max_retry_after: Duration,
+}
+
+impl RetryPolicy {
+    // Test clients replay recorded responses and must not wait.
+    #[cfg(test)]
+    pub(super) const NONE: Self = Self {
+        max_retries: 0,
+        base_delay: Duration::ZERO,
+        max_delay: Duration::ZERO,
+        max_retry_after: Duration::ZERO,
+    };
+
+    pub(super) const STANDARD: Self = Self {
+        max_retries: 3,
+        base_delay: Duration::from_millis(500),
+        max_delay: Duration::from_secs(8),
+        max_retry_after: Duration::from_secs(30),
+    };
+
+    /// Exponential backoff with equal jitter: half of the exponential step is fixed, the
+    /// other half is scaled by `jitter`, so retries never fire immediately but still spread.
+    pub(super) fn backoff_delay(&self, retry: u32, jitter: f64) -> Duration {
+        let exponential = self
+            .base_delay
+            .saturating_mul(2u32.saturating_pow(retry))
+            .min(self.max_delay);
+        let half = exponential / 2;
+        half + half.mul_f64(jitter.clamp(0.0, 1.0))
+    }
+}
+
+pub(super) struct Retrier {
+    pub policy: RetryPolicy,
+    pub sleep: Box<SleepFn>,
+    pub jitter: Box<JitterFn>,
+}
+
+impl Retrier {
+    #[cfg(test)]
+    pub(super) fn none() -> Self {
+        Self {
+            policy: RetryPolicy::NONE,
+            sleep: Box::new(|_| Box::pin(async {})),
+            jitter: Box::new(|| 0.0),
+        }
+    }
+
+    pub(super) fn standard() -> Self {
+        Self {
+            policy: RetryPolicy::STANDARD,
+            sleep: Box::new(|delay| Box::pin(tokio::time::sleep(delay))),
+            jitter: Box::new(random_fraction),
+        }
+    }
+
+    /// The wait before retry number `retry` (0-based), or None if the attempt should not be
+    /// repeated.
+    pub(super) fn delay_for(&self, retry: u32, retry_after: Option<Duration>) -> Option<Duration> {
+        if retry >= self.policy.max_retries {
+            return None;
+        }
+        match retry_after {
+            Some(delay) if delay > self.policy.max_retry_after => None,
+            Some(delay) => Some(delay),
+            None => Some(self.policy.backoff_delay(retry, (self.jitter)())),
+        }
+    }
+}
+
+pub(super) fn is_retryable_status(status: u16) -> bool {
+    status == 429 || (500..=599).contains(&status)
+}
+
+pub(super) fn is_retryable_error(error: &DayliteApiError) -> bool {
+    error.code == DayliteApiErrorCode::Timeout
+}
+
+/// Parses a Retry-After header: delta seconds or an HTTP date. Dates in the past mean no
+/// wait.
+pub(super) fn parse_retry_after(value: &str, now: DateTime<Utc>) -> Option<Duration> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u64>() {
+        return Some(Duration::from_secs(seconds));
+    }
+    let date = DateTime::parse_from_rfc2822(value).ok()?;
+    Some(
+        (date.with_timezone(&Utc) - now)
+            .to_std()
+            .unwrap_or(Duration::ZERO),
+    )
+}
+
+pub(super) fn with_retry_count(mut error: DayliteApiError, retries: u32) -> DayliteApiError {
+    if retries > 0 {
+        error.technical_message = format!(
+            "{}; nach {retries} Wiederholung(en)",
+            error.technical_message
+        );
+    }
+    error
+}
+
+pub(super) fn request_slots() -> &'static tokio::sync::Semaphore {
+    static SLOTS: std::sync::OnceLock<tokio::sync::Semaphore> = std::sync::OnceLock::new();
+    SLOTS.get_or_init(|| tokio::sync::Semaphore::new(MAX_CONCURRENT_REQUESTS))
+}
+
+// The crate has no RNG dependency; the random bits of a v4 UUID are good enough for jitter.
+fn random_fraction() -> f64 {
+    (uuid::Uuid::new_v4().as_u128() as u64 >> 11) as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::client::{DayliteApiClient, DayliteHttpMethod, DayliteHttpRequest};
+    use super::super::test_support::{mock_response, MockTransport};
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    fn recording_retrier(jitter: f64) -> (Retrier, Arc<Mutex<Vec<Duration>>>) {
+        let sleeps = Arc::new(Mutex::new(Vec::new()));
+        let recorded = Arc::clone(&sleeps);
+        let retrier = Retrier {
+            policy: RetryPolicy::STANDARD,
+            sleep: Box::new(move |delay| {
+                recorded.lock().unwrap().push(delay);
+                Box::pin(async {})
+            }),
+            jitter: Box::new(move || jitter),
+        };
+        (retrier, sleeps)
+    }
+
+    fn search_request() -> DayliteHttpRequest {
+        DayliteHttpRequest::new(DayliteHttpMethod::Post, "/projects/_search")
+    }
+
+    fn timeout_error() -> DayliteApiError {
+        DayliteApiError::new(
+            DayliteApiErrorCode::Timeout,
+            None,
+            "Zeitüberschreitung bei der Daylite-Anfrage.",
+            "Zeitüberschreitung bei /projects/_search",
+        )
+    }
+
+    #[test]
+    fn backoff_doubles_and_is_capped() {
+        let policy = RetryPolicy::STANDARD;
+
+        assert_eq!(policy.backoff_delay(0, 0.0), Duration::from_millis(250));
+        assert_eq!(policy.backoff_delay(0, 1.0), Duration::from_millis(500));
+        assert_eq!(policy.backoff_delay(2, 0.5), Duration::from_millis(1500));
+        assert_eq!(policy.backoff_delay(10, 1.0), Duration::from_secs(8));
+    }
+
+    #[test]
+    fn retry_after_accepts_seconds_and_http_dates() {
+        let now = DateTime::parse_from_rfc3339("2026-03-02T10:00:00Z")
+            .unwrap()
+            .with_timezone(&Utc);
+
+        assert_eq!(parse_retry_after(" 7 ", now), Some(Duration::from_secs(7)));
+        assert_eq!(
+            parse_retry_after("Mon, 02 Mar 2026 10:00:12 GMT", now),
+            Some(Duration::from_secs(12))
+        );
+        assert_eq!(
+            parse_retry_after("Mon, 02 Mar 2026 09:00:00 GMT", now),
+            Some(Duration::ZERO)
+        );
+        assert_eq!(parse_retry_after("bald", now), None);
+    }
+
+    #[test]
+    fn delay_respects_retry_after_and_limits() {
+        let (retrier, _) = recording_retrier(0.0);
+
+        assert_eq!(
+            retrier.delay_for(0, Some(Duration::from_secs(5))),
+            Some(Duration::from_secs(5))
+        );
+        assert_eq!(retrier.delay_for(0, Some(Duration::from_secs(120))), None);
+        assert_eq!(retrier.delay_for(3, None), None);
+    }
+
+    #[test]
+    fn retries_rate_limits_with_retry_after_then_succeeds() {
+        tauri::async_runtime::block_on(async {
+            let mut limited = mock_response(429, "{}");
+            limited.retry_after = Some("2".to_string());
+            let transport = MockTransport::new(vec![
+                Ok(limited),
+                Ok(mock_response(503, "{}")),
+                Ok(mock_response(200, r#"{"results":[]}"#)),
+            ]);
+            let (retrier, sleeps) = recording_retrier(0.5);
+            let client =
+                DayliteApiClient::with_transport(Box::new(transport.clone())).with_retrier(retrier);
+
+            let response = client
+                .send_request(search_request())
+                .await
+                .expect("third attempt should succeed");
+
+            assert_eq!(response.status, 200);
+            assert_eq!(transport.requests().len(), 3);
+            assert_eq!(
+                *sleeps.lock().unwrap(),
+                vec![Duration::from_secs(2), Duration::from_millis(750)]
+            );
+        });
+    }
+
+    #[test]
+    fn gives_up_after_max_retries_and_reports_the_count() {
+        tauri::async_runtime::block_on(async {
+            let transport = MockTransport::new(vec![
+                Ok(mock_response(500, "{}")),
+                Ok(mock_response(502, "{}")),
+                Err(timeout_error()),
+                Ok(mock_response(503, "{}")),
+            ]);
+            let (retrier, sleeps) = recording_retrier(0.0);
+            let client =
+                DayliteApiClient::with_transport(Box::new(transport.clone())).with_retrier(retrier);
+
+            let error = client
+                .send_request(search_request())
+                .await
+                .expect_err("persistent server errors should fail");
+
+            assert_eq!(error.code, DayliteApiErrorCode::ServerError);
+            assert_eq!(error.http_status, Some(503));
+            assert!(error.technical_message.ends_with("nach 3 Wiederholung(en)"));
+            assert_eq!(transport.requests().len(), 4);
+            assert_eq!(sleeps.lock().unwrap().len(), 3);
+        });
+    }
+
+    #[test]
+    fn timeouts_are_retried_but_other_failures_are_not() {
+        tauri::async_runtime::block_on(async {
+            let transport =
+                MockTransport::new(vec![Err(timeout_error()), Ok(mock_response(404, "{}"))]);
+            let (retrier, sleeps) = recording_retrier(0.0);
+            let client =
+                DayliteApiClient::with_transport(Box::new(transport.clone())).with_retrier(retrier);
+
+            let response = client
+                .send_request(search_request())
+                .await
+                .expect("404 is returned to the caller");
+
+            assert_eq!(response.status, 404);
+            assert_eq!(transport.requests().len(), 2);
+            assert_eq!(*sleeps.lock().unwrap(), vec![Duration::from_millis(250)]);
+        });
+    }
+
+    #[test]
+    fn non_retryable_requests_are_sent_once() {
+        tauri::async_runtime::block_on(async {
+            let transport =
+                MockTransport::new(vec![Err(timeout_error()), Ok(mock_response(201, "{}"))]);
+            let (retrier, sleeps) = recording_retrier(0.0);
+            let client =
+                DayliteApiClient::with_transport(Box::new(transport.clone())).with_retrier(retrier);
+
+            let error = client
+                .send_request(DayliteHttpRequest {
+                    retryable: false,
+                    ..DayliteHttpRequest::new(DayliteHttpMethod::Post, "/notes")
+                })
+                .await
+                .expect_err("the timeout is returned instead of repeating the create");
+
+            assert_eq!(error.code, DayliteApiErrorCode::Timeout);
+            assert_eq!(transport.requests().len(), 1);
+            assert!(sleeps.lock().unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn overlong_retry_after_is_not_waited_out() {
+        tauri::async_runtime::block_on(async {
+            let mut limited = mock_response(429, "{}");
+            limited.retry_after = Some("3600".to_string());
+            let transport = MockTransport::new(vec![Ok(limited)]);
+            let (retrier, sleeps) = recording_retrier(0.0);
+            let client =
+                DayliteApiClient::with_transport(Box::new(transport.clone())).with_retrier(retrier);
+
+            let response = client
+                .send_request(search_request())
+                .await
+                .expect("the first response is passed through");
+
+            assert_eq!(response.status, 429);
+            assert!(sleeps.lock().unwrap().is_empty());
+        });
+    }
+}
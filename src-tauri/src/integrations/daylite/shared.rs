@@ -4,6 +4,7 @@ use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
 use specta::Type;
 use std::time::{SystemTime, UNIX_EPOCH};
+use tauri::Emitter;
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
 #[serde(rename_all = "camelCase")]
@@ -30,6 +31,22 @@ pub struct DayliteSearchResult<T> {
     pub results: Vec<T>,
     #[serde(default)]
     pub next: Option<String>,
+    // Set when the results come from the local project cache instead of Daylite.
+    #[serde(default)]
+    pub from_cache: bool,
+}
+
+/// Emitted after every page while a listing follows Daylite's `next` links.
+pub const DAYLITE_PAGE_PROGRESS_EVENT: &str = "daylite-page-progress";
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DaylitePageProgress {
+    // Search endpoint being paged, e.g. "/projects/_search".
+    pub path: String,
+    pub page: u32,
+    // Records received so far, including this page.
+    pub loaded: u32,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
@@ -99,6 +116,23 @@ pub struct DayliteSearchInput {
     pub start: Option<String>,
     #[serde(default)]
     pub sort: Option<DayliteSearchSort>,
+    #[serde(default)]
+    pub category: Option<String>,
+    // Matches projects tagged with any of the keywords.
+    #[serde(default)]
+    pub keywords: Option<Vec<String>>,
+    // Inclusive YYYY-MM-DD bounds; either side may be open.
+    #[serde(default)]
+    pub due_from: Option<String>,
+    #[serde(default)]
+    pub due_to: Option<String>,
+    #[serde(default)]
+    pub modified_from: Option<String>,
+    #[serde(default)]
+    pub modified_to: Option<String>,
+    // Daylite reference of a company linked to the project, e.g. "/v1/companies/12".
+    #[serde(default)]
+    pub company_reference: Option<String>,
 }
 
 pub(super) fn build_limit_query(limit: Option<u16>) -> Vec<(String, String)> {
@@ -200,11 +234,23 @@ where
     F: FnOnce(DayliteApiClient, DayliteTokenState) -> Fut,
     Fut: std::future::Future<Output = Result<(T, DayliteTokenState), DayliteApiError>>,
 {
-    let store = load_store_or_error(app)?;
-    let client = DayliteApiClient::new(&store.api_endpoints.daylite_base_url)?;
+    let store = load_store_or_error(app.clone())?;
+    let client = DayliteApiClient::new(&store.api_endpoints.daylite_base_url)?
+        .with_page_progress(page_progress_emitter(app));
     with_token_refresh_lock(move |tokens| operation(client, tokens)).await
 }
 
+/// Forwards pagination progress to the frontend as [`DAYLITE_PAGE_PROGRESS_EVENT`].
+pub(super) fn page_progress_emitter(
+    app: tauri::AppHandle,
+) -> impl Fn(&DaylitePageProgress) + Send + Sync + 'static {
+    move |progress| {
+        if let Err(error) = app.emit(DAYLITE_PAGE_PROGRESS_EVENT, progress.clone()) {
+            eprintln!("daylite: page progress event failed: {error}");
+        }
+    }
+}
+
 pub(super) fn load_store_or_error(app: tauri::AppHandle) -> Result<LocalStore, DayliteApiError> {
     local_store::load_local_store(app).map_err(map_store_error)
 }
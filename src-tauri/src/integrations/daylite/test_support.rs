@@ -50,6 +50,7 @@ pub(super) fn mock_response(status: u16, body: &str) -> DayliteHttpResponse {
     DayliteHttpResponse {
         status,
         body: body.to_string(),
+        retry_after: None,
     }
 }
 
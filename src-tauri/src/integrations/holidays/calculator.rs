@@ -0,0 +1,265 @@
+use chrono::{Datelike, Duration, NaiveDate, Weekday};
+use std::collections::BTreeSet;
+
+use crate::integrations::local_store::CachedHoliday;
+
+/// Rule-based German public holidays for `year`, in the same shape as the Nager.Date data
+/// so both can be cached and filtered alike. The state rules reflect the law since 2018,
+/// plus the later additions (Frauentag, Weltkindertag) from the year they were introduced.
+pub(crate) fn german_holidays(year: i32) -> Vec<CachedHoliday> {
+    let Some(easter) = easter_sunday(year) else {
+        return vec![];
+    };
+    let fixed = |month: u32, day: u32| NaiveDate::from_ymd_opt(year, month, day);
+    let from_easter = |days: i64| Some(easter + Duration::days(days));
+
+    let reformation_states: &[&str] = if year == 2017 {
+        // 500th anniversary of the Reformation: a one-off nationwide holiday.
+        &[]
+    } else if year >= 2018 {
+        &[
+            "DE-BB", "DE-HB", "DE-HH", "DE-MV", "DE-NI", "DE-SH", "DE-SN", "DE-ST", "DE-TH",
+        ]
+    } else {
+        &["DE-BB", "DE-MV", "DE-SN", "DE-ST", "DE-TH"]
+    };
+    let womens_day_states: Vec<&str> = [("DE-BE", 2019), ("DE-MV", 2023)]
+        .into_iter()
+        .filter(|(_, since)| year >= *since)
+        .map(|(state, _)| state)
+        .collect();
+
+    let mut rules: Vec<(Option<NaiveDate>, &str, &[&str])> = vec![
+        (fixed(1, 1), "Neujahr", &[]),
+        (
+            fixed(1, 6),
+            "Heilige Drei Könige",
+            &["DE-BW", "DE-BY", "DE-ST"],
+        ),
+        (from_easter(-2), "Karfreitag", &[]),
+        (from_easter(0), "Ostersonntag", &["DE-BB"]),
+        (from_easter(1), "Ostermontag", &[]),
+        (fixed(5, 1), "Tag der Arbeit", &[]),
+        (from_easter(39), "Christi Himmelfahrt", &[]),
+        (from_easter(49), "Pfingstsonntag", &["DE-BB"]),
+        (from_easter(50), "Pfingstmontag", &[]),
+        (
+            from_easter(60),
+            "Fronleichnam",
+            &["DE-BW", "DE-BY", "DE-HE", "DE-NW", "DE-RP", "DE-SL"],
+        ),
+        (fixed(8, 15), "Mariä Himmelfahrt", &["DE-SL"]),
+        (fixed(10, 3), "Tag der Deutschen Einheit", &[]),
+        (fixed(10, 31), "Reformationstag", reformation_states),
+        (
+            fixed(11, 1),
+            "Allerheiligen",
+            &["DE-BW", "DE-BY", "DE-NW", "DE-RP", "DE-SL"],
+        ),
+        (repentance_day(year), "Buß- und Bettag", &["DE-SN"]),
+        (fixed(12, 25), "Erster Weihnachtstag", &[]),
+        (fixed(12, 26), "Zweiter Weihnachtstag", &[]),
+    ];
+    if !womens_day_states.is_empty() {
+        rules.push((
+            fixed(3, 8),
+            "Internationaler Frauentag",
+            womens_day_states.as_slice(),
+        ));
+    }
+    if year >= 2019 {
+        rules.push((fixed(9, 20), "Weltkindertag", &["DE-TH"]));
+    }
+    if year == 2020 || year == 2025 {
+        // Anniversary of the end of the war, only declared for round years.
+        rules.push((fixed(5, 8), "Tag der Befreiung", &["DE-BE"]));
+    }
+
+    let mut holidays: Vec<CachedHoliday> = rules
+        .into_iter()
+        .filter_map(|(date, name, states)| {
+            Some(CachedHoliday {
+                date: date?.format("%Y-%m-%d").to_string(),
+                name: name.to_string(),
+                global: states.is_empty(),
+                subdivisions: states.iter().map(|s| s.to_string()).collect(),
+            })
+        })
+        .collect();
+    holidays.sort_by(|a, b| a.date.cmp(&b.date));
+    holidays
+}
+
+/// Easter Sunday in the Gregorian calendar (anonymous Gregorian algorithm).
+pub(crate) fn easter_sunday(year: i32) -> Option<NaiveDate> {
+    let a = year % 19;
+    let b = year / 100;
+    let c = year % 100;
+    let d = b / 4;
+    let e = b % 4;
+    let f = (b + 8) / 25;
+    let g = (b - f + 1) / 3;
+    let h = (19 * a + b - d - g + 15) % 30;
+    let i = c / 4;
+    let k = c % 4;
+    let l = (32 + 2 * e + 2 * i - h - k) % 7;
+    let m = (a + 11 * h + 22 * l) / 451;
+    let month = (h + l - 7 * m + 114) / 31;
+    let day = (h + l - 7 * m + 114) % 31 + 1;
+    NaiveDate::from_ymd_opt(year, month as u32, day as u32)
+}
+
+/// Buß- und Bettag: the last Wednesday before 23 November.
+fn repentance_day(year: i32) -> Option<NaiveDate> {
+    let mut date = NaiveDate::from_ymd_opt(year, 11, 22)?;
+    while date.weekday() != Weekday::Wed {
+        date = date.pred_opt()?;
+    }
+    Some(date)
+}
+
+/// Compares holidays from another source with the calculated ones and describes every
+/// difference in which subdivisions a date is a holiday. Empty if both agree.
+pub(crate) fn cross_check(year: i32, holidays: &[CachedHoliday]) -> Vec<String> {
+    let expected = coverage(&german_holidays(year));
+    let actual = coverage(holidays);
+
+    let mut differences = Vec::new();
+    for key in expected.symmetric_difference(&actual) {
+        let (date, subdivision) = key;
+        let side = if expected.contains(key) {
+            "missing"
+        } else {
+            "unexpected"
+        };
+        differences.push(format!("{date} ({subdivision}): {side}"));
+    }
+    differences
+}
+
+// (date, subdivision) pairs, with "DE" standing for nationwide holidays.
+fn coverage(holidays: &[CachedHoliday]) -> BTreeSet<(String, String)> {
+    holidays
+        .iter()
+        .flat_map(|h| {
+            let subdivisions = if h.global {
+                vec!["DE".to_string()]
+            } else {
+                h.subdivisions.clone()
+            };
+            subdivisions
+                .into_iter()
+                .map(move |subdivision| (h.date.clone(), subdivision))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(raw: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").unwrap()
+    }
+
+    fn find<'a>(holidays: &'a [CachedHoliday], name: &str) -> &'a CachedHoliday {
+        holidays
+            .iter()
+            .find(|h| h.name == name)
+            .unwrap_or_else(|| panic!("{name} missing"))
+    }
+
+    #[test]
+    fn easter_sunday_matches_known_dates() {
+        assert_eq!(easter_sunday(2019), Some(date("2019-04-21")));
+        assert_eq!(easter_sunday(2024), Some(date("2024-03-31")));
+        assert_eq!(easter_sunday(2025), Some(date("2025-04-20")));
+        assert_eq!(easter_sunday(2026), Some(date("2026-04-05")));
+        assert_eq!(easter_sunday(2038), Some(date("2038-04-25")));
+    }
+
+    #[test]
+    fn repentance_day_is_the_wednesday_before_november_23() {
+        assert_eq!(repentance_day(2023), Some(date("2023-11-22")));
+        assert_eq!(repentance_day(2024), Some(date("2024-11-20")));
+        assert_eq!(repentance_day(2025), Some(date("2025-11-19")));
+    }
+
+    #[test]
+    fn movable_feasts_follow_easter_in_2025() {
+        let holidays = german_holidays(2025);
+
+        assert_eq!(find(&holidays, "Karfreitag").date, "2025-04-18");
+        assert_eq!(find(&holidays, "Ostermontag").date, "2025-04-21");
+        assert_eq!(find(&holidays, "Christi Himmelfahrt").date, "2025-05-29");
+        assert_eq!(find(&holidays, "Pfingstmontag").date, "2025-06-09");
+        assert_eq!(find(&holidays, "Fronleichnam").date, "2025-06-19");
+    }
+
+    #[test]
+    fn nationwide_holidays_for_2024() {
+        let holidays = german_holidays(2024);
+        let global: Vec<&str> = holidays
+            .iter()
+            .filter(|h| h.global)
+            .map(|h| h.date.as_str())
+            .collect();
+
+        assert_eq!(
+            global,
+            vec![
+                "2024-01-01",
+                "2024-03-29",
+                "2024-04-01",
+                "2024-05-01",
+                "2024-05-09",
+                "2024-05-20",
+                "2024-10-03",
+                "2024-12-25",
+                "2024-12-26",
+            ]
+        );
+    }
+
+    #[test]
+    fn state_rules_depend_on_the_year() {
+        let reformation_2017 = german_holidays(2017);
+        assert!(find(&reformation_2017, "Reformationstag").global);
+
+        let holidays_2024 = german_holidays(2024);
+        let reformation = find(&holidays_2024, "Reformationstag");
+        assert!(reformation.subdivisions.contains(&"DE-HH".to_string()));
+        assert!(!reformation.global);
+        let womens_day = find(&holidays_2024, "Internationaler Frauentag");
+        assert_eq!(womens_day.subdivisions, vec!["DE-BE", "DE-MV"]);
+
+        let holidays_2018 = german_holidays(2018);
+        assert!(!holidays_2018.iter().any(|h| h.name == "Weltkindertag"));
+        assert!(!holidays_2018
+            .iter()
+            .any(|h| h.name == "Internationaler Frauentag"));
+    }
+
+    #[test]
+    fn cross_check_reports_missing_and_unexpected_dates() {
+        let mut holidays = german_holidays(2024);
+        holidays.retain(|h| h.name != "Buß- und Bettag");
+        holidays.push(CachedHoliday {
+            date: "2024-08-08".to_string(),
+            name: "Friedensfest".to_string(),
+            global: false,
+            subdivisions: vec!["DE-BY".to_string()],
+        });
+
+        let differences = cross_check(2024, &holidays);
+
+        assert_eq!(
+            differences,
+            vec![
+                "2024-08-08 (DE-BY): unexpected".to_string(),
+                "2024-11-20 (DE-SN): missing".to_string(),
+            ]
+        );
+        assert!(cross_check(2024, &german_holidays(2024)).is_empty());
+    }
+}
@@ -0,0 +1,1209 @@
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::integrations::local_store::{
+    CachedHoliday, ClosureDay, ComputedHolidayCacheEntry, HolidayCacheEntry, IcsHolidayCacheEntry,
+    LocalStore,
+};
+use provider::{CalculatorProvider, HolidayProvider, IcsOrigin, IcsProvider, NagerProvider};
+
+mod calculator;
+mod provider;
+
+const CACHE_REFRESH_DAYS: i64 = 30;
+// Upper bound for range queries, so a typo cannot trigger dozens of Nager requests.
+const MAX_RANGE_YEARS: i32 = 10;
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct Holiday {
+    pub date: String,
+    pub name: String,
+    pub kind: HolidayKind,
+    pub source: HolidaySource,
+    // Name of the imported calendar for ICS entries.
+    pub source_name: Option<String>,
+    // Employee groups a closure is limited to; empty for public holidays and company-wide closures.
+    pub employee_groups: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum HolidayKind {
+    Public,
+    // Company-defined closure day from the store.
+    Closure,
+    // Entry of an imported ICS calendar; shown for planning, but employees still work.
+    SchoolHoliday,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum HolidaySource {
+    Nager,
+    // Built-in rules, used while Nager.Date is unreachable.
+    Calculator,
+    Ics,
+    // Closure days configured in the store.
+    Company,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct SubdivisionHolidays {
+    pub subdivision: String,
+    // Employees whose holidays follow this subdivision.
+    pub employee_references: Vec<String>,
+    pub holidays: Vec<Holiday>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportHolidayIcsInput {
+    pub name: String,
+    // Subdivision the calendar applies to; None = every employee.
+    pub subdivision: Option<String>,
+    // Feed URL, refreshed periodically. Takes precedence over `content`.
+    pub url: Option<String>,
+    // Content of an ICS file.
+    pub content: Option<String>,
+}
+
+/// Days off for the whole company in the week: public holidays of the default subdivision,
+/// then closure days that apply to every employee. School holidays and group closures are
+/// listed per subdivision by [`get_holidays_by_subdivision_for_week`].
+#[tauri::command]
+#[specta::specta]
+pub async fn get_holidays_for_week(
+    app: tauri::AppHandle,
+    week_start: String,
+) -> Result<Vec<Holiday>, String> {
+    let (week_start_date, week_end_date) = parse_week(&week_start)?;
+    let store = load_holiday_store(&app, week_start_date, week_end_date).await?;
+    Ok(company_holidays_in_range(
+        &store,
+        week_start_date,
+        week_end_date,
+    ))
+}
+
+/// Like [`get_holidays_for_week`], for any range of up to [`MAX_RANGE_YEARS`] calendar
+/// years. Both dates are inclusive.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_holidays_for_range(
+    app: tauri::AppHandle,
+    start_date: String,
+    end_date: String,
+) -> Result<Vec<Holiday>, String> {
+    let (start, end) = parse_range(&start_date, &end_date)?;
+    let store = load_holiday_store(&app, start, end).await?;
+    Ok(company_holidays_in_range(&store, start, end))
+}
+
+/// Like [`get_holidays_for_week`], for a whole calendar year.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_holidays_for_year(
+    app: tauri::AppHandle,
+    year: i32,
+) -> Result<Vec<Holiday>, String> {
+    let (start, end) = year_bounds(year)?;
+    let store = load_holiday_store(&app, start, end).await?;
+    Ok(company_holidays_in_range(&store, start, end))
+}
+
+/// Makes sure the holidays of `year` are cached in `store`, so the first week view of a new
+/// year does not wait for Nager.Date. Returns whether anything changed; the caller applies
+/// the result to the saved store with [`apply_holiday_caches`].
+pub(crate) async fn prefetch_holidays(store: &mut LocalStore, year: i32) -> Result<bool, String> {
+    let (start, end) = year_bounds(year)?;
+    refresh_holiday_caches(store, start, end).await
+}
+
+/// Holidays for the week, once per subdivision in use, each followed by the closure days
+/// that apply to at least one of its employees. The default subdivision is always listed,
+/// even if every employee has an override.
+#[tauri::command]
+#[specta::specta]
+pub async fn get_holidays_by_subdivision_for_week(
+    app: tauri::AppHandle,
+    week_start: String,
+) -> Result<Vec<SubdivisionHolidays>, String> {
+    let (week_start_date, week_end_date) = parse_week(&week_start)?;
+    let store = load_holiday_store(&app, week_start_date, week_end_date).await?;
+
+    Ok(employee_subdivisions(&store)
+        .into_iter()
+        .map(|(subdivision, employee_references)| {
+            let mut holidays =
+                holidays_in_range(&store, week_start_date, week_end_date, &subdivision);
+            holidays.extend(school_holidays_in_range(
+                &store,
+                week_start_date,
+                week_end_date,
+                &subdivision,
+            ));
+            holidays.extend(closures_in_range(
+                &store,
+                week_start_date,
+                week_end_date,
+                |closure| {
+                    closure.employee_groups.is_empty()
+                        || employee_references
+                            .iter()
+                            .any(|r| closure.applies_to_group(employee_group(&store, r)))
+                },
+            ));
+            SubdivisionHolidays {
+                holidays,
+                subdivision,
+                employee_references,
+            }
+        })
+        .collect())
+}
+
+/// Imports an ICS calendar, replacing one with the same name. Returns the number of
+/// imported days.
+#[tauri::command]
+#[specta::specta]
+pub async fn import_holiday_ics(
+    app: tauri::AppHandle,
+    input: ImportHolidayIcsInput,
+) -> Result<u32, String> {
+    let name = input.name.trim().to_string();
+    if name.is_empty() {
+        return Err("Bitte einen Namen für den Kalender angeben.".to_string());
+    }
+    let url = input
+        .url
+        .map(|url| url.trim().to_string())
+        .filter(|url| !url.is_empty());
+    let origin = match (&url, input.content) {
+        (Some(url), _) => IcsOrigin::Url(url.clone()),
+        (None, Some(content)) if !content.trim().is_empty() => IcsOrigin::Content(content),
+        _ => return Err("Bitte eine URL oder eine ICS-Datei angeben.".to_string()),
+    };
+    let subdivision = input.subdivision.filter(|s| !s.trim().is_empty());
+    let holidays = IcsProvider {
+        origin,
+        subdivision: subdivision.clone(),
+    }
+    .load()
+    .await?;
+    let imported_days = holidays.len() as u32;
+
+    let mut store = load_store(&app)?;
+    store.ics_holiday_calendars.retain(|c| c.name != name);
+    store.ics_holiday_calendars.push(IcsHolidayCacheEntry {
+        name,
+        url,
+        subdivision,
+        fetched_at: chrono::Utc::now()
+            .date_naive()
+            .format("%Y-%m-%d")
+            .to_string(),
+        holidays,
+    });
+    save_store(&app, store)?;
+    Ok(imported_days)
+}
+
+#[tauri::command]
+#[specta::specta]
+pub async fn remove_holiday_ics(app: tauri::AppHandle, name: String) -> Result<(), String> {
+    let mut store = load_store(&app)?;
+    let count_before = store.ics_holiday_calendars.len();
+    store.ics_holiday_calendars.retain(|c| c.name != name);
+    if store.ics_holiday_calendars.len() != count_before {
+        save_store(&app, store)?;
+    }
+    Ok(())
+}
+
+fn parse_week(week_start: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let week_start_date = NaiveDate::parse_from_str(week_start, "%Y-%m-%d").map_err(|e| {
+        eprintln!("holidays: invalid week_start '{week_start}': {e}");
+        "Feiertage konnten nicht geladen werden".to_string()
+    })?;
+    Ok((week_start_date, week_start_date + chrono::Duration::days(6)))
+}
+
+fn parse_range(start_date: &str, end_date: &str) -> Result<(NaiveDate, NaiveDate), String> {
+    let parse = |raw: &str| {
+        NaiveDate::parse_from_str(raw, "%Y-%m-%d").map_err(|e| {
+            eprintln!("holidays: invalid date '{raw}': {e}");
+            "Feiertage konnten nicht geladen werden".to_string()
+        })
+    };
+    let (start, end) = (parse(start_date)?, parse(end_date)?);
+    if end < start {
+        return Err("Das Enddatum liegt vor dem Startdatum.".to_string());
+    }
+    if end.year() - start.year() >= MAX_RANGE_YEARS {
+        return Err(format!(
+            "Feiertage können für höchstens {MAX_RANGE_YEARS} Jahre auf einmal geladen werden."
+        ));
+    }
+    Ok((start, end))
+}
+
+fn year_bounds(year: i32) -> Result<(NaiveDate, NaiveDate), String> {
+    match (
+        NaiveDate::from_ymd_opt(year, 1, 1),
+        NaiveDate::from_ymd_opt(year, 12, 31),
+    ) {
+        (Some(start), Some(end)) => Ok((start, end)),
+        _ => {
+            eprintln!("holidays: invalid year {year}");
+            Err("Feiertage konnten nicht geladen werden".to_string())
+        }
+    }
+}
+
+fn load_store(app: &tauri::AppHandle) -> Result<LocalStore, String> {
+    crate::integrations::local_store::load_local_store(app.clone()).map_err(|e| {
+        eprintln!("holidays: store load failed: {}", e.technical_message);
+        "Feiertage konnten nicht geladen werden".to_string()
+    })
+}
+
+fn save_store(app: &tauri::AppHandle, store: LocalStore) -> Result<(), String> {
+    crate::integrations::local_store::save_store_internal(app, store).map_err(|e| {
+        eprintln!("holidays: store save failed: {}", e.technical_message);
+        "Feiertage konnten nicht geladen werden".to_string()
+    })
+}
+
+/// Loads the store and makes sure the holiday caches cover `start..=end` and imported ICS
+/// feeds are current. If anything was fetched, the caches are applied to a freshly loaded
+/// store and saved once, so edits saved while the requests ran are kept.
+async fn load_holiday_store(
+    app: &tauri::AppHandle,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<LocalStore, String> {
+    let mut store = load_store(app)?;
+    if refresh_holiday_caches(&mut store, start, end).await? {
+        let mut latest = load_store(app)?;
+        apply_holiday_caches(&mut latest, &store);
+        save_store(app, latest)?;
+    }
+    Ok(store)
+}
+
+/// Fills the holiday caches of `store` for `start..=end` and refreshes imported ICS feeds.
+/// Missing years are fetched concurrently. Returns whether any cache changed.
+async fn refresh_holiday_caches(
+    store: &mut LocalStore,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> Result<bool, String> {
+    let today = chrono::Utc::now().date_naive();
+    let today_str = today.format("%Y-%m-%d").to_string();
+    let current_year = today.year();
+
+    let missing_years: Vec<i32> = years_in_range(start, end)
+        .into_iter()
+        .filter(|year| {
+            let cached = store.holiday_cache.iter().find(|e| e.year == *year);
+            needs_fetch(cached, current_year, today)
+        })
+        .collect();
+    let providers: Vec<NagerProvider> = missing_years
+        .iter()
+        .map(|&year| NagerProvider { year })
+        .collect();
+    let results = futures::future::join_all(providers.iter().map(|p| p.load())).await;
+
+    let mut changed = false;
+    for (year, result) in missing_years.into_iter().zip(results) {
+        match result {
+            Ok(holidays) => {
+                let differences = calculator::cross_check(year, &holidays);
+                if !differences.is_empty() {
+                    eprintln!(
+                        "holidays: Nager data for {year} differs from the local rules: {}",
+                        differences.join(", ")
+                    );
+                }
+                store.holiday_cache.retain(|e| e.year != year);
+                store.holiday_cache.push(HolidayCacheEntry {
+                    year,
+                    fetched_at: today_str.clone(),
+                    holidays,
+                    all_subdivisions: true,
+                });
+                store.computed_holiday_cache.retain(|e| e.year != year);
+                changed = true;
+            }
+            Err(_) if store.computed_holiday_cache.iter().any(|e| e.year == year) => {}
+            Err(_) => {
+                eprintln!("holidays: using locally calculated holidays for {year}");
+                let holidays = (CalculatorProvider { year }).load().await?;
+                store
+                    .computed_holiday_cache
+                    .push(ComputedHolidayCacheEntry {
+                        year,
+                        computed_at: today_str.clone(),
+                        holidays,
+                    });
+                changed = true;
+            }
+        }
+    }
+
+    for calendar in &mut store.ics_holiday_calendars {
+        let Some(url) = calendar.url.clone() else {
+            continue;
+        };
+        if is_fresh(&calendar.fetched_at, today) {
+            continue;
+        }
+        let provider = IcsProvider {
+            origin: IcsOrigin::Url(url),
+            subdivision: calendar.subdivision.clone(),
+        };
+        // A failed refresh keeps the previous data; the next week load tries again.
+        if let Ok(holidays) = provider.load().await {
+            calendar.holidays = holidays;
+            calendar.fetched_at = today_str.clone();
+            changed = true;
+        }
+    }
+
+    Ok(changed)
+}
+
+/// Copies the holiday caches of `refreshed` into `store`, year by year and calendar by
+/// calendar. ICS calendars removed, re-pointed or re-imported in the meantime are left as
+/// they are.
+pub(crate) fn apply_holiday_caches(store: &mut LocalStore, refreshed: &LocalStore) {
+    for entry in &refreshed.holiday_cache {
+        store.holiday_cache.retain(|e| e.year != entry.year);
+        store.holiday_cache.push(entry.clone());
+    }
+    for entry in &refreshed.computed_holiday_cache {
+        if !store
+            .computed_holiday_cache
+            .iter()
+            .any(|e| e.year == entry.year)
+        {
+            store.computed_holiday_cache.push(entry.clone());
+        }
+    }
+    let nager_years: Vec<i32> = nager_entries(store).map(|e| e.year).collect();
+    store
+        .computed_holiday_cache
+        .retain(|e| !nager_years.contains(&e.year));
+    for calendar in &mut store.ics_holiday_calendars {
+        if let Some(fetched) = refreshed
+            .ics_holiday_calendars
+            .iter()
+            .find(|r| r.name == calendar.name && r.url.is_some() && r.url == calendar.url)
+            .filter(|r| r.fetched_at > calendar.fetched_at)
+        {
+            calendar.holidays = fetched.holidays.clone();
+            calendar.fetched_at = fetched.fetched_at.clone();
+        }
+    }
+}
+
+/// Cached public holidays between `start` and `end` (inclusive) that apply in `subdivision`.
+/// Calculated holidays are only used for years without usable Nager.Date data.
+pub(crate) fn holidays_in_range(
+    store: &LocalStore,
+    start: NaiveDate,
+    end: NaiveDate,
+    subdivision: &str,
+) -> Vec<Holiday> {
+    let nager = nager_entries(store).map(|e| (HolidaySource::Nager, &e.holidays));
+    let computed = store
+        .computed_holiday_cache
+        .iter()
+        .filter(|e| !nager_entries(store).any(|n| n.year == e.year))
+        .map(|e| (HolidaySource::Calculator, &e.holidays));
+
+    nager
+        .chain(computed)
+        .flat_map(|(source, holidays)| holidays.iter().map(move |h| (source, h)))
+        .filter(|(_, h)| applies_in(h, subdivision) && in_range(h, start, end))
+        .map(|(source, h)| Holiday {
+            date: h.date.clone(),
+            name: h.name.clone(),
+            kind: HolidayKind::Public,
+            source,
+            source_name: None,
+            employee_groups: vec![],
+        })
+        .collect()
+}
+
+/// Entries of imported ICS calendars between `start` and `end` (inclusive) that apply in
+/// `subdivision`.
+fn school_holidays_in_range(
+    store: &LocalStore,
+    start: NaiveDate,
+    end: NaiveDate,
+    subdivision: &str,
+) -> Vec<Holiday> {
+    store
+        .ics_holiday_calendars
+        .iter()
+        .flat_map(|calendar| calendar.holidays.iter().map(move |h| (calendar, h)))
+        .filter(|(_, h)| applies_in(h, subdivision) && in_range(h, start, end))
+        .map(|(calendar, h)| Holiday {
+            date: h.date.clone(),
+            name: h.name.clone(),
+            kind: HolidayKind::SchoolHoliday,
+            source: HolidaySource::Ics,
+            source_name: Some(calendar.name.clone()),
+            employee_groups: vec![],
+        })
+        .collect()
+}
+
+fn in_range(holiday: &CachedHoliday, start: NaiveDate, end: NaiveDate) -> bool {
+    NaiveDate::parse_from_str(&holiday.date, "%Y-%m-%d")
+        .is_ok_and(|date| date >= start && date <= end)
+}
+
+/// One entry per date of every closure day in `start..=end` accepted by `include`.
+fn closures_in_range(
+    store: &LocalStore,
+    start: NaiveDate,
+    end: NaiveDate,
+    include: impl Fn(&ClosureDay) -> bool,
+) -> Vec<Holiday> {
+    let mut closures: Vec<Holiday> = store
+        .closure_days
+        .iter()
+        .filter(|closure| include(closure))
+        .flat_map(|closure| {
+            closure
+                .dates_within(start, end)
+                .into_iter()
+                .map(move |date| Holiday {
+                    date: date.format("%Y-%m-%d").to_string(),
+                    name: closure.name.clone(),
+                    kind: HolidayKind::Closure,
+                    source: HolidaySource::Company,
+                    source_name: None,
+                    employee_groups: closure.employee_groups.clone(),
+                })
+        })
+        .collect();
+    closures.sort_by(|a, b| a.date.cmp(&b.date));
+    closures
+}
+
+/// Public holidays of the default subdivision and company-wide closure days in
+/// `start..=end`, in that order: the days nobody works.
+fn company_holidays_in_range(store: &LocalStore, start: NaiveDate, end: NaiveDate) -> Vec<Holiday> {
+    let subdivision = store.default_holiday_subdivision();
+    let mut holidays = holidays_in_range(store, start, end, subdivision);
+    holidays.extend(closures_in_range(store, start, end, |closure| {
+        closure.employee_groups.is_empty()
+    }));
+    holidays
+}
+
+/// Dates in `start..=end` on which the employee does not work: public holidays of their
+/// subdivision and closure days that apply to them. Only cached holidays are considered.
+pub(crate) fn days_off(
+    store: &LocalStore,
+    daylite_contact_reference: &str,
+    start: NaiveDate,
+    end: NaiveDate,
+) -> BTreeSet<String> {
+    let group = employee_group(store, daylite_contact_reference);
+    holidays_in_range(
+        store,
+        start,
+        end,
+        store.holiday_subdivision_for(daylite_contact_reference),
+    )
+    .into_iter()
+    .chain(closures_in_range(store, start, end, |closure| {
+        closure.applies_to_group(group)
+    }))
+    .map(|holiday| holiday.date)
+    .collect()
+}
+
+fn employee_group<'a>(store: &'a LocalStore, daylite_contact_reference: &str) -> Option<&'a str> {
+    store
+        .employee_settings
+        .iter()
+        .find(|s| s.daylite_contact_reference == daylite_contact_reference)
+        .and_then(|s| s.employee_group.as_deref())
+}
+
+/// Subdivisions in use, each with the employees it applies to, sorted by subdivision.
+fn employee_subdivisions(store: &LocalStore) -> BTreeMap<String, Vec<String>> {
+    let mut subdivisions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    subdivisions.insert(store.default_holiday_subdivision().to_string(), vec![]);
+    for setting in &store.employee_settings {
+        let reference = &setting.daylite_contact_reference;
+        subdivisions
+            .entry(store.holiday_subdivision_for(reference).to_string())
+            .or_default()
+            .push(reference.clone());
+    }
+    subdivisions
+}
+
+/// Nager.Date entries in the current format. Entries from before all subdivisions were
+/// kept have no `global` or `subdivisions` data, so none of their holidays would apply;
+/// they are ignored until refetched.
+fn nager_entries(store: &LocalStore) -> impl Iterator<Item = &HolidayCacheEntry> {
+    store.holiday_cache.iter().filter(|e| e.all_subdivisions)
+}
+
+fn applies_in(holiday: &CachedHoliday, subdivision: &str) -> bool {
+    holiday.global || holiday.subdivisions.iter().any(|s| s == subdivision)
+}
+
+fn years_in_range(start: NaiveDate, end: NaiveDate) -> Vec<i32> {
+    (start.year()..=end.year()).collect()
+}
+
+/// Past and future years never change once fetched; the current year is refreshed
+/// periodically. Outdated-format entries are always refetched.
+fn needs_fetch(cached: Option<&HolidayCacheEntry>, current_year: i32, today: NaiveDate) -> bool {
+    match cached {
+        None => true,
+        Some(entry) if !entry.all_subdivisions => true,
+        Some(entry) => entry.year == current_year && !is_cache_entry_fresh(entry, today),
+    }
+}
+
+fn is_cache_entry_fresh(entry: &HolidayCacheEntry, today: NaiveDate) -> bool {
+    is_fresh(&entry.fetched_at, today)
+}
+
+fn is_fresh(fetched_at: &str, today: NaiveDate) -> bool {
+    let fetched = NaiveDate::parse_from_str(fetched_at, "%Y-%m-%d")
+        .unwrap_or_else(|_| NaiveDate::from_ymd_opt(2000, 1, 1).unwrap());
+    (today - fetched).num_days() <= CACHE_REFRESH_DAYS
+}
+
+#[cfg(test)]
+mod tests {
+    use super::provider::{to_cached_holiday, NagerHoliday};
+    use super::*;
+    use crate::integrations::local_store::EmployeeSetting;
+
+    fn holidays_in(nager: Vec<NagerHoliday>, subdivision: &str) -> Vec<CachedHoliday> {
+        nager
+            .into_iter()
+            .map(to_cached_holiday)
+            .filter(|h| applies_in(h, subdivision))
+            .collect()
+    }
+
+    fn employee(reference: &str, subdivision: Option<&str>) -> EmployeeSetting {
+        EmployeeSetting {
+            daylite_contact_reference: reference.to_string(),
+            holiday_subdivision: subdivision.map(str::to_string),
+            ..EmployeeSetting::default()
+        }
+    }
+
+    #[test]
+    fn state_holiday_applies_in_the_listed_subdivisions_only() {
+        let allerheiligen = || {
+            vec![NagerHoliday {
+                date: "2024-11-01".to_string(),
+                local_name: "Allerheiligen".to_string(),
+                global: false,
+                counties: Some(vec!["DE-BY".to_string(), "DE-BW".to_string()]),
+            }]
+        };
+
+        assert_eq!(holidays_in(allerheiligen(), "DE-BY").len(), 1);
+        assert!(holidays_in(allerheiligen(), "DE-MV").is_empty());
+    }
+
+    #[test]
+    fn employee_subdivisions_groups_employees_and_keeps_the_default() {
+        let store = LocalStore {
+            default_holiday_subdivision: Some("DE-BW".to_string()),
+            employee_settings: vec![
+                employee("/v1/contacts/1", Some("DE-BY")),
+                employee("/v1/contacts/2", None),
+                employee("/v1/contacts/3", Some("DE-BY")),
+            ],
+            ..LocalStore::default()
+        };
+
+        let groups = employee_subdivisions(&store);
+
+        assert_eq!(
+            groups.get("DE-BY"),
+            Some(&vec![
+                "/v1/contacts/1".to_string(),
+                "/v1/contacts/3".to_string()
+            ])
+        );
+        assert_eq!(
+            groups.get("DE-BW"),
+            Some(&vec!["/v1/contacts/2".to_string()])
+        );
+        assert_eq!(groups.len(), 2);
+    }
+
+    #[test]
+    fn holidays_in_range_filters_by_subdivision_and_date() {
+        let store = LocalStore {
+            holiday_cache: vec![HolidayCacheEntry {
+                year: 2024,
+                fetched_at: "2024-06-01".to_string(),
+                holidays: vec![
+                    CachedHoliday {
+                        date: "2024-10-31".to_string(),
+                        name: "Reformationstag".to_string(),
+                        global: false,
+                        subdivisions: vec!["DE-MV".to_string()],
+                    },
+                    CachedHoliday {
+                        date: "2024-11-01".to_string(),
+                        name: "Allerheiligen".to_string(),
+                        global: false,
+                        subdivisions: vec!["DE-BY".to_string()],
+                    },
+                    CachedHoliday {
+                        date: "2024-12-25".to_string(),
+                        name: "1. Weihnachtstag".to_string(),
+                        global: true,
+                        subdivisions: vec![],
+                    },
+                ],
+                all_subdivisions: true,
+            }],
+            ..LocalStore::default()
+        };
+        let start = NaiveDate::from_ymd_opt(2024, 10, 28).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 11, 3).unwrap();
+
+        let bavaria = holidays_in_range(&store, start, end, "DE-BY");
+
+        assert_eq!(bavaria.len(), 1);
+        assert_eq!(bavaria[0].name, "Allerheiligen");
+    }
+
+    #[test]
+    fn calculated_holidays_fill_years_without_nager_data_and_are_tagged() {
+        let holiday = |date: &str, name: &str| CachedHoliday {
+            date: date.to_string(),
+            name: name.to_string(),
+            global: true,
+            subdivisions: vec![],
+        };
+        let store = LocalStore {
+            holiday_cache: vec![HolidayCacheEntry {
+                year: 2024,
+                fetched_at: "2024-06-01".to_string(),
+                holidays: vec![holiday("2024-12-25", "1. Weihnachtstag")],
+                all_subdivisions: true,
+            }],
+            computed_holiday_cache: vec![
+                ComputedHolidayCacheEntry {
+                    year: 2024,
+                    computed_at: "2024-05-01".to_string(),
+                    holidays: vec![holiday("2024-12-25", "Erster Weihnachtstag")],
+                },
+                ComputedHolidayCacheEntry {
+                    year: 2025,
+                    computed_at: "2024-12-01".to_string(),
+                    holidays: vec![holiday("2025-01-01", "Neujahr")],
+                },
+            ],
+            ..LocalStore::default()
+        };
+        let start = NaiveDate::from_ymd_opt(2024, 12, 23).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 5).unwrap();
+
+        let holidays = holidays_in_range(&store, start, end, "DE-MV");
+
+        let tagged: Vec<(&str, HolidaySource)> = holidays
+            .iter()
+            .map(|h| (h.name.as_str(), h.source))
+            .collect();
+        assert_eq!(
+            tagged,
+            vec![
+                ("1. Weihnachtstag", HolidaySource::Nager),
+                ("Neujahr", HolidaySource::Calculator),
+            ]
+        );
+    }
+
+    #[test]
+    fn old_format_entries_do_not_hide_calculated_holidays() {
+        // Cached before all subdivisions were kept: no `global` or `subdivisions` data.
+        let old = HolidayCacheEntry {
+            year: 2025,
+            fetched_at: "2024-12-01".to_string(),
+            holidays: vec![CachedHoliday {
+                date: "2025-01-01".to_string(),
+                name: "Neujahr".to_string(),
+                global: false,
+                subdivisions: vec![],
+            }],
+            all_subdivisions: false,
+        };
+        let computed = ComputedHolidayCacheEntry {
+            year: 2025,
+            computed_at: "2025-01-02".to_string(),
+            holidays: vec![CachedHoliday {
+                date: "2025-01-01".to_string(),
+                name: "Neujahr".to_string(),
+                global: true,
+                subdivisions: vec![],
+            }],
+        };
+        let mut store = LocalStore {
+            holiday_cache: vec![old.clone()],
+            ..LocalStore::default()
+        };
+        let refreshed = LocalStore {
+            holiday_cache: vec![old],
+            computed_holiday_cache: vec![computed],
+            ..LocalStore::default()
+        };
+        let day = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        apply_holiday_caches(&mut store, &refreshed);
+        let holidays = holidays_in_range(&store, day, day, "DE-MV");
+
+        assert_eq!(store.computed_holiday_cache.len(), 1);
+        assert_eq!(holidays.len(), 1);
+        assert_eq!(holidays[0].source, HolidaySource::Calculator);
+        assert!(days_off(&store, "/v1/contacts/1", day, day).contains("2025-01-01"));
+    }
+
+    #[test]
+    fn school_holidays_follow_the_calendar_subdivision() {
+        let store = LocalStore {
+            ics_holiday_calendars: vec![IcsHolidayCacheEntry {
+                name: "Schulferien MV".to_string(),
+                url: None,
+                subdivision: Some("DE-MV".to_string()),
+                fetched_at: "2025-09-01".to_string(),
+                holidays: vec![CachedHoliday {
+                    date: "2025-10-20".to_string(),
+                    name: "Herbstferien".to_string(),
+                    global: false,
+                    subdivisions: vec!["DE-MV".to_string()],
+                }],
+            }],
+            ..LocalStore::default()
+        };
+        let start = NaiveDate::from_ymd_opt(2025, 10, 20).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 10, 26).unwrap();
+
+        let mv = school_holidays_in_range(&store, start, end, "DE-MV");
+
+        assert_eq!(mv.len(), 1);
+        assert_eq!(mv[0].kind, HolidayKind::SchoolHoliday);
+        assert_eq!(mv[0].source, HolidaySource::Ics);
+        assert_eq!(mv[0].source_name.as_deref(), Some("Schulferien MV"));
+        assert!(school_holidays_in_range(&store, start, end, "DE-BY").is_empty());
+        assert!(holidays_in_range(&store, start, end, "DE-MV").is_empty());
+    }
+
+    #[test]
+    fn applying_caches_keeps_edits_saved_in_the_meantime() {
+        let nager = |year: i32| HolidayCacheEntry {
+            year,
+            fetched_at: "2025-12-01".to_string(),
+            holidays: vec![],
+            all_subdivisions: true,
+        };
+        let feed = |fetched_at: &str| IcsHolidayCacheEntry {
+            name: "Schulferien MV".to_string(),
+            url: Some("https://ferien.example/mv.ics".to_string()),
+            subdivision: Some("DE-MV".to_string()),
+            fetched_at: fetched_at.to_string(),
+            holidays: vec![],
+        };
+        let refreshed = LocalStore {
+            holiday_cache: vec![nager(2026)],
+            ics_holiday_calendars: vec![feed("2025-12-01")],
+            ..LocalStore::default()
+        };
+        let mut store = LocalStore {
+            closure_days: vec![ClosureDay {
+                name: "Inventur".to_string(),
+                start_date: "2026-01-02".to_string(),
+                end_date: None,
+                employee_groups: vec![],
+            }],
+            holiday_cache: vec![nager(2025)],
+            computed_holiday_cache: vec![ComputedHolidayCacheEntry {
+                year: 2026,
+                computed_at: "2025-11-01".to_string(),
+                holidays: vec![],
+            }],
+            ics_holiday_calendars: vec![feed("2025-11-01")],
+            ..LocalStore::default()
+        };
+
+        apply_holiday_caches(&mut store, &refreshed);
+
+        assert_eq!(store.closure_days.len(), 1);
+        let years: Vec<i32> = store.holiday_cache.iter().map(|e| e.year).collect();
+        assert_eq!(years, vec![2025, 2026]);
+        assert!(store.computed_holiday_cache.is_empty());
+        assert_eq!(store.ics_holiday_calendars[0].fetched_at, "2025-12-01");
+
+        // A calendar removed while the feeds were fetched stays removed.
+        store.ics_holiday_calendars.clear();
+        apply_holiday_caches(&mut store, &refreshed);
+        assert!(store.ics_holiday_calendars.is_empty());
+    }
+
+    #[test]
+    fn days_off_include_closures_for_the_employee_group() {
+        let mut fitter = employee("/v1/contacts/1", None);
+        fitter.employee_group = Some("Montage".to_string());
+        let store = LocalStore {
+            employee_settings: vec![fitter, employee("/v1/contacts/2", None)],
+            closure_days: vec![
+                ClosureDay {
+                    name: "Betriebsferien".to_string(),
+                    start_date: "2024-12-23".to_string(),
+                    end_date: Some("2024-12-27".to_string()),
+                    employee_groups: vec![],
+                },
+                ClosureDay {
+                    name: "Inventur".to_string(),
+                    start_date: "2024-12-30".to_string(),
+                    end_date: None,
+                    employee_groups: vec!["Büro".to_string()],
+                },
+            ],
+            ..LocalStore::default()
+        };
+        let start = NaiveDate::from_ymd_opt(2024, 12, 26).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+
+        let fitter_days = days_off(&store, "/v1/contacts/1", start, end);
+        let ungrouped_days = days_off(&store, "/v1/contacts/2", start, end);
+
+        assert_eq!(
+            fitter_days.into_iter().collect::<Vec<_>>(),
+            vec!["2024-12-26".to_string(), "2024-12-27".to_string()]
+        );
+        assert_eq!(ungrouped_days.len(), 2);
+        let closures = closures_in_range(&store, start, end, |_| true);
+        assert_eq!(closures.len(), 3);
+        assert_eq!(closures[2].name, "Inventur");
+        assert_eq!(closures[2].kind, HolidayKind::Closure);
+    }
+
+    #[test]
+    fn includes_global_holidays() {
+        let nager = vec![NagerHoliday {
+            date: "2024-01-01".to_string(),
+            local_name: "Neujahr".to_string(),
+            global: true,
+            counties: None,
+        }];
+        let result = holidays_in(nager, "DE-MV");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "Neujahr");
+        assert_eq!(result[0].date, "2024-01-01");
+    }
+
+    #[test]
+    fn includes_de_mv_specific_holidays() {
+        let nager = vec![NagerHoliday {
+            date: "2024-10-31".to_string(),
+            local_name: "Reformationstag".to_string(),
+            global: false,
+            counties: Some(vec!["DE-MV".to_string(), "DE-HH".to_string()]),
+        }];
+        let result = holidays_in(nager, "DE-MV");
+        assert_eq!(result.len(), 1);
+        assert_eq!(result[0].name, "Reformationstag");
+    }
+
+    #[test]
+    fn excludes_other_state_holidays() {
+        let nager = vec![NagerHoliday {
+            date: "2024-11-01".to_string(),
+            local_name: "Allerheiligen".to_string(),
+            global: false,
+            counties: Some(vec!["DE-BY".to_string(), "DE-BW".to_string()]),
+        }];
+        let result = holidays_in(nager, "DE-MV");
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn excludes_non_global_holidays_with_no_counties() {
+        let nager = vec![NagerHoliday {
+            date: "2024-06-01".to_string(),
+            local_name: "Lokaler Feiertag".to_string(),
+            global: false,
+            counties: None,
+        }];
+        let result = holidays_in(nager, "DE-MV");
+        assert_eq!(result.len(), 0);
+    }
+
+    #[test]
+    fn uses_local_name_as_holiday_name() {
+        let nager = vec![NagerHoliday {
+            date: "2024-12-25".to_string(),
+            local_name: "1. Weihnachtstag".to_string(),
+            global: true,
+            counties: None,
+        }];
+        let result = holidays_in(nager, "DE-MV");
+        assert_eq!(result[0].name, "1. Weihnachtstag");
+    }
+
+    #[test]
+    fn single_year_range_returns_one_year() {
+        let start = NaiveDate::from_ymd_opt(2024, 6, 3).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 6, 9).unwrap();
+        assert_eq!(years_in_range(start, end), vec![2024]);
+    }
+
+    #[test]
+    fn year_boundary_range_returns_two_years() {
+        let start = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 5).unwrap();
+        assert_eq!(years_in_range(start, end), vec![2024, 2025]);
+    }
+
+    #[test]
+    fn multi_year_range_returns_every_year() {
+        let start = NaiveDate::from_ymd_opt(2024, 11, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2027, 2, 28).unwrap();
+        assert_eq!(years_in_range(start, end), vec![2024, 2025, 2026, 2027]);
+    }
+
+    #[test]
+    fn range_must_be_ordered_and_bounded() {
+        assert_eq!(
+            parse_range("2025-01-01", "2025-12-31"),
+            Ok((
+                NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2025, 12, 31).unwrap()
+            ))
+        );
+        assert!(parse_range("2025-02-01", "2025-01-31").is_err());
+        assert!(parse_range("2025-01-01", "2034-12-31").is_ok());
+        assert!(parse_range("2025-01-01", "2035-01-01").is_err());
+        assert!(parse_range("2025-13-01", "2025-12-31").is_err());
+    }
+
+    #[test]
+    fn company_holidays_are_days_off_for_everyone() {
+        let store = LocalStore {
+            holiday_cache: vec![HolidayCacheEntry {
+                year: 2025,
+                fetched_at: "2025-01-02".to_string(),
+                holidays: vec![CachedHoliday {
+                    date: "2025-12-25".to_string(),
+                    name: "1. Weihnachtstag".to_string(),
+                    global: true,
+                    subdivisions: vec![],
+                }],
+                all_subdivisions: true,
+            }],
+            closure_days: vec![
+                ClosureDay {
+                    name: "Betriebsferien".to_string(),
+                    start_date: "2025-12-22".to_string(),
+                    end_date: Some("2025-12-23".to_string()),
+                    employee_groups: vec![],
+                },
+                ClosureDay {
+                    name: "Inventur".to_string(),
+                    start_date: "2025-12-29".to_string(),
+                    end_date: None,
+                    employee_groups: vec!["Büro".to_string()],
+                },
+            ],
+            ics_holiday_calendars: vec![IcsHolidayCacheEntry {
+                name: "Schulferien".to_string(),
+                url: None,
+                subdivision: None,
+                fetched_at: "2025-09-01".to_string(),
+                holidays: vec![CachedHoliday {
+                    date: "2025-12-24".to_string(),
+                    name: "Weihnachtsferien".to_string(),
+                    global: true,
+                    subdivisions: vec![],
+                }],
+            }],
+            ..LocalStore::default()
+        };
+        let start = NaiveDate::from_ymd_opt(2025, 12, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 12, 31).unwrap();
+
+        let kinds: Vec<(String, HolidayKind)> = company_holidays_in_range(&store, start, end)
+            .into_iter()
+            .map(|h| (h.date, h.kind))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                ("2025-12-25".to_string(), HolidayKind::Public),
+                ("2025-12-22".to_string(), HolidayKind::Closure),
+                ("2025-12-23".to_string(), HolidayKind::Closure),
+            ]
+        );
+    }
+
+    #[test]
+    fn week_filter_merges_holidays_from_two_years() {
+        let start = NaiveDate::from_ymd_opt(2024, 12, 30).unwrap();
+        let end = NaiveDate::from_ymd_opt(2025, 1, 5).unwrap();
+
+        let all_holidays = [
+            Holiday {
+                date: "2024-12-25".to_string(),
+                name: "1. Weihnachtstag".to_string(),
+                kind: HolidayKind::Public,
+                source: HolidaySource::Nager,
+                source_name: None,
+                employee_groups: vec![],
+            },
+            Holiday {
+                date: "2024-12-31".to_string(),
+                name: "Silvester".to_string(),
+                kind: HolidayKind::Public,
+                source: HolidaySource::Nager,
+                source_name: None,
+                employee_groups: vec![],
+            },
+            Holiday {
+                date: "2025-01-01".to_string(),
+                name: "Neujahr".to_string(),
+                kind: HolidayKind::Public,
+                source: HolidaySource::Nager,
+                source_name: None,
+                employee_groups: vec![],
+            },
+            Holiday {
+                date: "2025-04-18".to_string(),
+                name: "Karfreitag".to_string(),
+                kind: HolidayKind::Public,
+                source: HolidaySource::Nager,
+                source_name: None,
+                employee_groups: vec![],
+            },
+        ];
+
+        let filtered: Vec<&Holiday> = all_holidays
+            .iter()
+            .filter(|h| {
+                let date = NaiveDate::parse_from_str(&h.date, "%Y-%m-%d").unwrap();
+                date >= start && date <= end
+            })
+            .collect();
+
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().any(|h| h.date == "2024-12-31"));
+        assert!(filtered.iter().any(|h| h.date == "2025-01-01"));
+    }
+
+    #[test]
+    fn week_filter_includes_holidays_on_weekend_days() {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 26).unwrap();
+        let end = start + chrono::Duration::days(6);
+        assert_eq!(end, NaiveDate::from_ymd_opt(2026, 2, 1).unwrap());
+
+        let all_holidays = [
+            Holiday {
+                date: "2026-01-31".to_string(), // Saturday
+                name: "Samstagsfeiertag".to_string(),
+                kind: HolidayKind::Public,
+                source: HolidaySource::Nager,
+                source_name: None,
+                employee_groups: vec![],
+            },
+            Holiday {
+                date: "2026-02-01".to_string(), // Sunday (week end)
+                name: "Sonntagsfeiertag".to_string(),
+                kind: HolidayKind::Public,
+                source: HolidaySource::Nager,
+                source_name: None,
+                employee_groups: vec![],
+            },
+        ];
+
+        let filtered: Vec<&Holiday> = all_holidays
+            .iter()
+            .filter(|h| {
+                let date = NaiveDate::parse_from_str(&h.date, "%Y-%m-%d").unwrap();
+                date >= start && date <= end
+            })
+            .collect();
+
+        assert_eq!(filtered.len(), 2);
+        assert!(filtered.iter().any(|h| h.date == "2026-01-31"));
+        assert!(filtered.iter().any(|h| h.date == "2026-02-01"));
+    }
+
+    #[test]
+    fn cache_entry_is_fresh_within_30_days() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let entry = HolidayCacheEntry {
+            year: 2024,
+            fetched_at: "2024-06-10".to_string(),
+            holidays: vec![],
+            all_subdivisions: true,
+        };
+        assert!(is_cache_entry_fresh(&entry, today));
+    }
+
+    #[test]
+    fn cache_entry_is_stale_after_30_days() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let entry = HolidayCacheEntry {
+            year: 2024,
+            fetched_at: "2024-05-29".to_string(),
+            holidays: vec![],
+            all_subdivisions: true,
+        };
+        assert!(!is_cache_entry_fresh(&entry, today));
+    }
+
+    #[test]
+    fn cache_entry_is_fresh_exactly_at_30_days() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let entry = HolidayCacheEntry {
+            year: 2024,
+            fetched_at: "2024-05-31".to_string(),
+            holidays: vec![],
+            all_subdivisions: true,
+        };
+        assert!(is_cache_entry_fresh(&entry, today));
+    }
+
+    #[test]
+    fn legacy_entries_are_refetched() {
+        let today = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        let entry = |all_subdivisions: bool| HolidayCacheEntry {
+            year: 2025,
+            fetched_at: "2024-06-29".to_string(),
+            holidays: vec![],
+            all_subdivisions,
+        };
+
+        assert!(!needs_fetch(Some(&entry(true)), 2024, today));
+        assert!(needs_fetch(Some(&entry(false)), 2024, today));
+        assert!(needs_fetch(None, 2024, today));
+    }
+}
@@ -0,0 +1,217 @@
+use chrono::NaiveDate;
+use serde::Deserialize;
+use std::future::Future;
+use std::pin::Pin;
+use std::time::Duration;
+use tauri_plugin_http::reqwest;
+
+use super::calculator;
+use crate::integrations::calendar::ical::parse_ical_events;
+use crate::integrations::local_store::CachedHoliday;
+
+const NAGER_BASE_URL: &str = "https://date.nager.at/api/v3/PublicHolidays";
+const REQUEST_TIMEOUT_SECS: u64 = 5;
+// Guards against open-ended or mistyped events expanding into thousands of days.
+const MAX_ICS_EVENT_DAYS: i64 = 366;
+
+pub(super) type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+/// A source of holiday data. Every provider has its own cache entry type in the store, so
+/// the data of one source never overwrites that of another.
+pub(super) trait HolidayProvider: Send + Sync {
+    fn load<'a>(&'a self) -> BoxFuture<'a, Result<Vec<CachedHoliday>, String>>;
+}
+
+/// Public holidays of one year from Nager.Date.
+pub(super) struct NagerProvider {
+    pub year: i32,
+}
+
+impl HolidayProvider for NagerProvider {
+    fn load<'a>(&'a self) -> BoxFuture<'a, Result<Vec<CachedHoliday>, String>> {
+        Box::pin(async move {
+            let url = format!("{NAGER_BASE_URL}/{}/DE", self.year);
+            let body = fetch_text(&url, "Feiertage konnten nicht geladen werden").await?;
+            let nager_holidays: Vec<NagerHoliday> = serde_json::from_str(&body).map_err(|e| {
+                eprintln!("holidays: JSON parse failed: {e}");
+                "Feiertage konnten nicht geladen werden".to_string()
+            })?;
+            Ok(nager_holidays.into_iter().map(to_cached_holiday).collect())
+        })
+    }
+}
+
+/// Public holidays of one year from the built-in rules; works offline.
+pub(super) struct CalculatorProvider {
+    pub year: i32,
+}
+
+impl HolidayProvider for CalculatorProvider {
+    fn load<'a>(&'a self) -> BoxFuture<'a, Result<Vec<CachedHoliday>, String>> {
+        Box::pin(async move { Ok(calculator::german_holidays(self.year)) })
+    }
+}
+
+/// Every day covered by the events of an ICS calendar, e.g. an official Schulferien feed.
+pub(super) struct IcsProvider {
+    pub origin: IcsOrigin,
+    // Subdivision the calendar applies to; None = every employee.
+    pub subdivision: Option<String>,
+}
+
+pub(super) enum IcsOrigin {
+    Url(String),
+    Content(String),
+}
+
+impl HolidayProvider for IcsProvider {
+    fn load<'a>(&'a self) -> BoxFuture<'a, Result<Vec<CachedHoliday>, String>> {
+        Box::pin(async move {
+            match &self.origin {
+                IcsOrigin::Url(url) => {
+                    let body = fetch_text(url, "ICS-Kalender konnte nicht geladen werden").await?;
+                    ics_holidays(&body, self.subdivision.as_deref())
+                }
+                IcsOrigin::Content(content) => ics_holidays(content, self.subdivision.as_deref()),
+            }
+        })
+    }
+}
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct NagerHoliday {
+    pub date: String,
+    pub local_name: String,
+    pub global: bool,
+    pub counties: Option<Vec<String>>,
+}
+
+pub(super) fn to_cached_holiday(holiday: NagerHoliday) -> CachedHoliday {
+    CachedHoliday {
+        date: holiday.date,
+        name: holiday.local_name,
+        global: holiday.global,
+        subdivisions: holiday.counties.unwrap_or_default(),
+    }
+}
+
+/// One entry per day and event, sorted by date. All-day events end the day before their
+/// DTEND, as in iCalendar; events without DTEND cover their start day only.
+fn ics_holidays(ical_text: &str, subdivision: Option<&str>) -> Result<Vec<CachedHoliday>, String> {
+    let events = parse_ical_events(ical_text).map_err(|e| {
+        eprintln!("holidays: ICS parse failed: {e}");
+        "Die ICS-Datei konnte nicht gelesen werden.".to_string()
+    })?;
+
+    let mut holidays = Vec::new();
+    for event in events {
+        let Ok(start) = NaiveDate::parse_from_str(&event.dtstart, "%Y-%m-%d") else {
+            continue;
+        };
+        let end = event
+            .dtend
+            .filter(|end| *end > start)
+            .unwrap_or(start + chrono::Duration::days(1));
+        if (end - start).num_days() > MAX_ICS_EVENT_DAYS {
+            eprintln!(
+                "holidays: skipping ICS event '{}' spanning {} days",
+                event.summary,
+                (end - start).num_days()
+            );
+            continue;
+        }
+        holidays.extend(
+            start
+                .iter_days()
+                .take_while(|day| *day < end)
+                .map(|day| CachedHoliday {
+                    date: day.format("%Y-%m-%d").to_string(),
+                    name: event.summary.trim().to_string(),
+                    global: subdivision.is_none(),
+                    subdivisions: subdivision.map(str::to_string).into_iter().collect(),
+                }),
+        );
+    }
+    holidays.sort_by(|a, b| a.date.cmp(&b.date).then_with(|| a.name.cmp(&b.name)));
+    holidays.dedup();
+    Ok(holidays)
+}
+
+async fn fetch_text(url: &str, user_message: &str) -> Result<String, String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .build()
+        .map_err(|_| user_message.to_string())?;
+
+    let response = client.get(url).send().await.map_err(|e| {
+        eprintln!("holidays: HTTP request to {url} failed: {e}");
+        user_message.to_string()
+    })?;
+
+    if !response.status().is_success() {
+        eprintln!("holidays: {url} returned status {}", response.status());
+        return Err(user_message.to_string());
+    }
+
+    response.text().await.map_err(|e| {
+        eprintln!("holidays: reading response body failed: {e}");
+        user_message.to_string()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCHULFERIEN: &str = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nBEGIN:VEVENT\r\nUID:herbst-2025\r\nSUMMARY:Herbstferien\r\nDTSTART;VALUE=DATE:20251020\r\nDTEND;VALUE=DATE:20251025\r\nEND:VEVENT\r\nBEGIN:VEVENT\r\nUID:reformation-2025\r\nSUMMARY:Reformationstag (beweglicher Ferientag)\r\nDTSTART;VALUE=DATE:20251031\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+    #[test]
+    fn ics_events_expand_to_one_entry_per_day() {
+        let holidays = ics_holidays(SCHULFERIEN, Some("DE-MV")).unwrap();
+
+        let dates: Vec<&str> = holidays.iter().map(|h| h.date.as_str()).collect();
+        assert_eq!(
+            dates,
+            vec![
+                "2025-10-20",
+                "2025-10-21",
+                "2025-10-22",
+                "2025-10-23",
+                "2025-10-24",
+                "2025-10-31",
+            ]
+        );
+        assert_eq!(holidays[0].name, "Herbstferien");
+        assert!(!holidays[0].global);
+        assert_eq!(holidays[0].subdivisions, vec!["DE-MV"]);
+    }
+
+    #[test]
+    fn ics_calendar_without_subdivision_applies_everywhere() {
+        let holidays = ics_holidays(SCHULFERIEN, None).unwrap();
+
+        assert!(holidays
+            .iter()
+            .all(|h| h.global && h.subdivisions.is_empty()));
+    }
+
+    #[test]
+    fn ics_events_spanning_more_than_a_year_are_skipped() {
+        let ical = "BEGIN:VCALENDAR\r\nBEGIN:VEVENT\r\nUID:x\r\nSUMMARY:Tippfehler\r\nDTSTART;VALUE=DATE:20250101\r\nDTEND;VALUE=DATE:20290101\r\nEND:VEVENT\r\nEND:VCALENDAR\r\n";
+
+        assert!(ics_holidays(ical, None).unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn offline_providers_load_without_network() {
+        let ics = IcsProvider {
+            origin: IcsOrigin::Content(SCHULFERIEN.to_string()),
+            subdivision: None,
+        };
+        let calculator = CalculatorProvider { year: 2025 };
+
+        assert_eq!(ics.load().await.unwrap().len(), 6);
+        assert!(!calculator.load().await.unwrap().is_empty());
+    }
+}
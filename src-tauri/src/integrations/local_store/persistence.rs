@@ -124,8 +124,9 @@ fn save_store_to_path(path: &Path, store: &LocalStore) -> Result<(), StoreError>
 mod tests {
     use super::*;
     use crate::integrations::local_store::types::{
-        ApiEndpoints, CachedHoliday, DayliteCache, DayliteContactCacheEntry, DayliteContactUrl,
-        DayliteProjectCacheEntry, DisplaySettings, EmployeeSetting, HolidayCacheEntry,
+        AbsenceSettings, ApiEndpoints, CachedHoliday, DayliteCache, DayliteContactCacheEntry,
+        DayliteContactUrl, DayliteProjectCacheEntry, DisplaySettings, EmployeeSetting,
+        HolidayCacheEntry,
     };
     use std::fs;
     use std::path::PathBuf;
@@ -159,6 +160,9 @@ mod tests {
                 primary_ical_last_test_passed: Some(true),
                 absence_ical_last_tested_at: None,
                 absence_ical_last_test_passed: None,
+                working_time_model: None,
+                holiday_subdivision: None,
+                employee_group: None,
             }],
             display_settings: DisplaySettings {
                 hide_non_plannable_employees: false,
@@ -166,10 +170,13 @@ mod tests {
             },
             daylite_cache: DayliteCache {
                 last_synced_at: Some("2026-02-13T12:00:00Z".to_string()),
+                projects_synced_at: Some("2026-02-13T11:58:00Z".to_string()),
+                contacts_synced_at: Some("2026-02-13T12:00:00Z".to_string()),
                 projects: vec![DayliteProjectCacheEntry {
                     reference: "/v1/projects/1".to_string(),
                     name: "Projekt Nord".to_string(),
                     status: "in_progress".to_string(),
+                    ..DayliteProjectCacheEntry::default()
                 }],
                 contacts: vec![DayliteContactCacheEntry {
                     reference: "/v1/contacts/1".to_string(),
@@ -182,6 +189,7 @@ mod tests {
                         note: None,
                     }],
                 }],
+                project_details: vec![],
             },
             holiday_cache: vec![HolidayCacheEntry {
                 year: 2026,
@@ -189,8 +197,12 @@ mod tests {
                 holidays: vec![CachedHoliday {
                     date: "2026-01-01".to_string(),
                     name: "Neujahr".to_string(),
+                    global: true,
+                    subdivisions: vec![],
                 }],
+                all_subdivisions: true,
             }],
+            ..LocalStore::default()
         };
 
         save_store_to_path(&test_path, &store).expect("save should succeed");
@@ -265,7 +277,10 @@ mod tests {
                 holidays: vec![CachedHoliday {
                     date: "2025-01-01".to_string(),
                     name: "Neujahr".to_string(),
+                    global: true,
+                    subdivisions: vec![],
                 }],
+                all_subdivisions: true,
             }],
             ..LocalStore::default()
         };
@@ -292,6 +307,7 @@ mod tests {
 
         let loaded = load_store_from_path(&test_path).expect("should load without holidayCache");
         assert!(loaded.holiday_cache.is_empty());
+        assert_eq!(loaded.absence_settings, AbsenceSettings::default());
         assert!(loaded.display_settings.hide_non_plannable_employees);
     }
 
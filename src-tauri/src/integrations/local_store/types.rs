@@ -12,18 +12,85 @@ pub struct LocalStore {
     pub daylite_cache: DayliteCache,
     #[serde(default)]
     pub holiday_cache: Vec<HolidayCacheEntry>,
+    #[serde(default)]
+    pub absence_settings: AbsenceSettings,
+    /// Company-wide model for employees without their own `working_time_model`.
+    #[serde(default)]
+    pub default_working_time_model: WorkingTimeModel,
+    /// Bundesland (ISO 3166-2, e.g. "DE-BY") whose public holidays apply to employees
+    /// without their own `holiday_subdivision`. None = [`FALLBACK_HOLIDAY_SUBDIVISION`].
+    #[serde(default)]
+    pub default_holiday_subdivision: Option<String>,
+    /// Company closures such as the Christmas break or bridge days.
+    #[serde(default)]
+    pub closure_days: Vec<ClosureDay>,
+    /// Locally calculated public holidays for years Nager.Date could not be reached for.
+    #[serde(default)]
+    pub computed_holiday_cache: Vec<ComputedHolidayCacheEntry>,
+    /// Imported ICS calendars such as official Schulferien; informational, not days off.
+    #[serde(default)]
+    pub ics_holiday_calendars: Vec<IcsHolidayCacheEntry>,
+    #[serde(default)]
+    pub background_sync: BackgroundSyncSettings,
+    #[serde(default)]
+    pub assignment_mirror: AssignmentMirrorSettings,
+    #[serde(default)]
+    pub project_status_automation: ProjectStatusAutomation,
+    #[serde(default)]
+    pub planning_contacts: PlanningContactSettings,
 }
 
+/// The company's home state, used until a default subdivision is configured.
+pub const FALLBACK_HOLIDAY_SUBDIVISION: &str = "DE-MV";
+
 impl LocalStore {
     pub fn cleanup_holiday_cache(&mut self, today: NaiveDate) {
         let one_year_ago = today
             .checked_sub_months(chrono::Months::new(12))
             .unwrap_or(today);
-        self.holiday_cache.retain(|entry| {
-            NaiveDate::parse_from_str(&entry.fetched_at, "%Y-%m-%d")
+        let is_recent = |raw: &str| {
+            NaiveDate::parse_from_str(raw, "%Y-%m-%d")
                 .map(|d| d > one_year_ago)
                 .unwrap_or(true)
-        });
+        };
+        self.holiday_cache
+            .retain(|entry| is_recent(&entry.fetched_at));
+        self.computed_holiday_cache
+            .retain(|entry| is_recent(&entry.computed_at));
+    }
+
+    pub fn working_time_model_for(&self, daylite_contact_reference: &str) -> &WorkingTimeModel {
+        self.employee_settings
+            .iter()
+            .find(|s| s.daylite_contact_reference == daylite_contact_reference)
+            .and_then(|s| s.working_time_model.as_ref())
+            .unwrap_or(&self.default_working_time_model)
+    }
+
+    pub fn default_holiday_subdivision(&self) -> &str {
+        self.default_holiday_subdivision
+            .as_deref()
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or(FALLBACK_HOLIDAY_SUBDIVISION)
+    }
+
+    pub fn holiday_subdivision_for(&self, daylite_contact_reference: &str) -> &str {
+        self.employee_settings
+            .iter()
+            .find(|s| s.daylite_contact_reference == daylite_contact_reference)
+            .and_then(|s| s.holiday_subdivision.as_deref())
+            .filter(|s| !s.trim().is_empty())
+            .unwrap_or_else(|| self.default_holiday_subdivision())
+    }
+
+    /// Finds the employee whose primary calendar is `calendar_url`, ignoring trailing slashes.
+    pub fn employee_for_primary_calendar(&self, calendar_url: &str) -> Option<&EmployeeSetting> {
+        let target = calendar_url.trim_end_matches('/');
+        self.employee_settings.iter().find(|s| {
+            s.zep_primary_calendar
+                .as_deref()
+                .is_some_and(|url| !url.is_empty() && url.trim_end_matches('/') == target)
+        })
     }
 }
 
@@ -53,6 +120,85 @@ pub struct EmployeeSetting {
     pub absence_ical_last_tested_at: Option<String>,
     #[serde(default)]
     pub absence_ical_last_test_passed: Option<bool>,
+    /// None = the company default model applies.
+    #[serde(default)]
+    pub working_time_model: Option<WorkingTimeModel>,
+    /// None = the company default subdivision applies.
+    #[serde(default)]
+    pub holiday_subdivision: Option<String>,
+    /// Free-form group name (e.g. "Werkstatt") that closure days can be limited to.
+    #[serde(default)]
+    pub employee_group: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum WorkingDay {
+    Monday,
+    Tuesday,
+    Wednesday,
+    Thursday,
+    Friday,
+    Saturday,
+    Sunday,
+}
+
+impl WorkingDay {
+    pub fn from_weekday(weekday: chrono::Weekday) -> Self {
+        match weekday {
+            chrono::Weekday::Mon => Self::Monday,
+            chrono::Weekday::Tue => Self::Tuesday,
+            chrono::Weekday::Wed => Self::Wednesday,
+            chrono::Weekday::Thu => Self::Thursday,
+            chrono::Weekday::Fri => Self::Friday,
+            chrono::Weekday::Sat => Self::Saturday,
+            chrono::Weekday::Sun => Self::Sunday,
+        }
+    }
+}
+
+/// Weekly working time of an employee. Times are HH:MM in local time.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkingTimeModel {
+    pub working_days: Vec<WorkingDay>,
+    pub start_time: String,
+    pub end_time: String,
+    /// Contracted hours per week; the staffing report weighs person-days with it.
+    pub weekly_hours: u32,
+}
+
+impl WorkingTimeModel {
+    pub fn works_on(&self, date: NaiveDate) -> bool {
+        use chrono::Datelike;
+        self.working_days
+            .contains(&WorkingDay::from_weekday(date.weekday()))
+    }
+
+    /// Contracted hours of one working day; zero for a model without working days.
+    pub fn daily_hours(&self) -> f64 {
+        if self.working_days.is_empty() {
+            return 0.0;
+        }
+        f64::from(self.weekly_hours) / self.working_days.len() as f64
+    }
+}
+
+impl Default for WorkingTimeModel {
+    fn default() -> Self {
+        Self {
+            working_days: vec![
+                WorkingDay::Monday,
+                WorkingDay::Tuesday,
+                WorkingDay::Wednesday,
+                WorkingDay::Thursday,
+                WorkingDay::Friday,
+            ],
+            start_time: "08:00".to_string(),
+            end_time: "16:00".to_string(),
+            weekly_hours: 40,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
@@ -72,12 +218,204 @@ impl Default for DisplaySettings {
     }
 }
 
+/// Intervals of the refreshes the app runs in the background while it is open.
+/// Off until enabled in the settings; an interval of 0 turns that refresh off.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct BackgroundSyncSettings {
+    pub enabled: bool,
+    pub contacts_interval_minutes: u32,
+    pub projects_interval_minutes: u32,
+    /// Pre-fetches next year's public holidays.
+    pub holidays_interval_minutes: u32,
+    /// Re-tests every assigned ZEP calendar URL.
+    pub calendar_probe_interval_minutes: u32,
+}
+
+impl Default for BackgroundSyncSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            contacts_interval_minutes: 60,
+            projects_interval_minutes: 15,
+            holidays_interval_minutes: 24 * 60,
+            calendar_probe_interval_minutes: 6 * 60,
+        }
+    }
+}
+
+/// Which Daylite contacts are plannable employees, and the URL labels under which their
+/// ZEP calendars are kept on the contact. Both compare case-insensitively.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct PlanningContactSettings {
+    pub categories: Vec<String>,
+    pub primary_ical_label: String,
+    pub absence_ical_label: String,
+}
+
+const DEFAULT_PLANNING_CATEGORIES: [&str; 2] = ["Monteur", "Test"];
+const DEFAULT_PRIMARY_ICAL_LABEL: &str = "Einsatz iCal";
+const DEFAULT_ABSENCE_ICAL_LABEL: &str = "Abwesenheit iCal";
+
+impl Default for PlanningContactSettings {
+    fn default() -> Self {
+        Self {
+            categories: DEFAULT_PLANNING_CATEGORIES.map(String::from).to_vec(),
+            primary_ical_label: DEFAULT_PRIMARY_ICAL_LABEL.to_string(),
+            absence_ical_label: DEFAULT_ABSENCE_ICAL_LABEL.to_string(),
+        }
+    }
+}
+
+impl PlanningContactSettings {
+    /// The configured categories, trimmed. A list left empty falls back to the defaults,
+    /// as a contact sync without categories would drop every employee from the cache.
+    pub fn planning_categories(&self) -> Vec<String> {
+        let categories: Vec<String> = self
+            .categories
+            .iter()
+            .map(|category| category.trim().to_string())
+            .filter(|category| !category.is_empty())
+            .collect();
+        if categories.is_empty() {
+            return DEFAULT_PLANNING_CATEGORIES.map(String::from).to_vec();
+        }
+        categories
+    }
+
+    pub fn primary_label(&self) -> &str {
+        non_empty_or(&self.primary_ical_label, DEFAULT_PRIMARY_ICAL_LABEL)
+    }
+
+    pub fn absence_label(&self) -> &str {
+        non_empty_or(&self.absence_ical_label, DEFAULT_ABSENCE_ICAL_LABEL)
+    }
+}
+
+fn non_empty_or<'a>(value: &'a str, fallback: &'a str) -> &'a str {
+    match value.trim() {
+        "" => fallback,
+        trimmed => trimmed,
+    }
+}
+
+/// Mirrors planned assignments into a note on their Daylite project, so the office sees
+/// who is planned when without opening the planner. Off by default.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AssignmentMirrorSettings {
+    pub enabled: bool,
+}
+
+/// Status changes applied to a project in Daylite when an assignment is planned on it.
+/// The first rule whose `from_status` matches wins; done, cancelled and abandoned projects
+/// are never changed, whatever the rules say. Off by default; the default rule is a preset
+/// for when it is switched on.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ProjectStatusAutomation {
+    pub enabled: bool,
+    pub rules: Vec<ProjectStatusRule>,
+}
+
+impl Default for ProjectStatusAutomation {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            rules: vec![ProjectStatusRule {
+                from_status: "new_status".to_string(),
+                to_status: "in_progress".to_string(),
+                set_started: true,
+            }],
+        }
+    }
+}
+
+/// Daylite status values, e.g. "new_status" or "in_progress".
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectStatusRule {
+    pub from_status: String,
+    pub to_status: String,
+    /// Sets the project's `started` date to the assignment's day unless it already has one.
+    #[serde(default)]
+    pub set_started: bool,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum AbsenceCategory {
+    Vacation,
+    Sick,
+    SpecialLeave,
+    School,
+    Other,
+}
+
+/// Maps absence events to a category. CATEGORIES values must match exactly and
+/// summary keywords are substrings; both comparisons ignore case.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AbsenceCategoryRule {
+    pub category: AbsenceCategory,
+    #[serde(default)]
+    pub summary_keywords: Vec<String>,
+    #[serde(default)]
+    pub ical_categories: Vec<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct AbsenceSettings {
+    /// Checked in order; the first matching rule wins. Unmatched absences are `Other`.
+    pub category_rules: Vec<AbsenceCategoryRule>,
+}
+
+impl Default for AbsenceSettings {
+    fn default() -> Self {
+        let rule =
+            |category, summary_keywords: &[&str], ical_categories: &[&str]| AbsenceCategoryRule {
+                category,
+                summary_keywords: summary_keywords.iter().map(|s| s.to_string()).collect(),
+                ical_categories: ical_categories.iter().map(|s| s.to_string()).collect(),
+            };
+        Self {
+            category_rules: vec![
+                // Before Vacation: "Sonderurlaub" contains "urlaub".
+                rule(
+                    AbsenceCategory::SpecialLeave,
+                    &["sonderurlaub"],
+                    &["Sonderurlaub"],
+                ),
+                rule(AbsenceCategory::Vacation, &["urlaub"], &["Urlaub"]),
+                rule(AbsenceCategory::Sick, &["krank"], &["Krank", "Krankheit"]),
+                rule(
+                    AbsenceCategory::School,
+                    &["schule"],
+                    &["Schule", "Berufsschule"],
+                ),
+            ],
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
 #[serde(rename_all = "camelCase")]
 pub struct DayliteCache {
+    /// Last time any Daylite data in this cache was written, including single contact updates.
     pub last_synced_at: Option<String>,
+    /// Start of the last successful project sync; incremental syncs fetch changes since then.
+    #[serde(default)]
+    pub projects_synced_at: Option<String>,
+    /// Last time the planning contacts were loaded completely.
+    #[serde(default)]
+    pub contacts_synced_at: Option<String>,
     pub projects: Vec<DayliteProjectCacheEntry>,
     pub contacts: Vec<DayliteContactCacheEntry>,
+    /// Project details loaded on demand; reused until they are older than the details TTL.
+    #[serde(default)]
+    pub project_details: Vec<DayliteProjectDetailsCacheEntry>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
@@ -86,6 +424,90 @@ pub struct DayliteProjectCacheEntry {
     pub reference: String,
     pub name: String,
     pub status: String,
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Due date as an RFC 3339 UTC timestamp, as in `PlanningProjectRecord`.
+    #[serde(default)]
+    pub due: Option<String>,
+    #[serde(default)]
+    pub keywords: Vec<String>,
+    #[serde(default)]
+    pub modify_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DayliteProjectDetailsCacheEntry {
+    pub fetched_at: String,
+    pub details: DayliteProjectDetails,
+}
+
+/// What planners need to know about a project site beyond its status.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DayliteProjectDetails {
+    pub reference: String,
+    pub name: String,
+    /// The project's own address, or else the linked company's.
+    #[serde(default)]
+    pub site_address: Option<DayliteAddress>,
+    #[serde(default)]
+    pub company: Option<DayliteLinkedCompany>,
+    /// The first linked contact that is not a planning contact (employee).
+    #[serde(default)]
+    pub contact_person: Option<DayliteContactPerson>,
+    /// Newest first.
+    #[serde(default)]
+    pub recent_notes: Vec<DayliteProjectNote>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DayliteAddress {
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub street: Option<String>,
+    #[serde(default)]
+    pub postal_code: Option<String>,
+    #[serde(default)]
+    pub city: Option<String>,
+    #[serde(default)]
+    pub country: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DayliteLinkedCompany {
+    pub reference: String,
+    pub name: String,
+    #[serde(default)]
+    pub phone: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DayliteContactPerson {
+    pub reference: String,
+    pub name: String,
+    /// Role of the contact on the project, e.g. "Bauleiter".
+    #[serde(default)]
+    pub role: Option<String>,
+    #[serde(default)]
+    pub phone: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct DayliteProjectNote {
+    pub reference: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub text: Option<String>,
+    /// Last modification, or creation if Daylite reports no modification.
+    #[serde(default)]
+    pub date: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
@@ -109,12 +531,82 @@ pub struct DayliteContactUrl {
     pub note: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct ClosureDay {
+    pub name: String,
+    /// YYYY-MM-DD.
+    pub start_date: String,
+    /// Inclusive YYYY-MM-DD; None for a single day.
+    #[serde(default)]
+    pub end_date: Option<String>,
+    /// Groups the closure applies to; empty = every employee.
+    #[serde(default)]
+    pub employee_groups: Vec<String>,
+}
+
+impl ClosureDay {
+    /// Dates covered by the closure within `start..=end`. A closure with an unparsable or
+    /// inverted range covers nothing.
+    pub fn dates_within(&self, start: NaiveDate, end: NaiveDate) -> Vec<NaiveDate> {
+        let Ok(first) = NaiveDate::parse_from_str(&self.start_date, "%Y-%m-%d") else {
+            return vec![];
+        };
+        let last = match self.end_date.as_deref() {
+            None => first,
+            Some(raw) => match NaiveDate::parse_from_str(raw, "%Y-%m-%d") {
+                Ok(last) => last,
+                Err(_) => return vec![],
+            },
+        };
+        first
+            .max(start)
+            .iter_days()
+            .take_while(|date| *date <= last.min(end))
+            .collect()
+    }
+
+    pub fn applies_to_group(&self, employee_group: Option<&str>) -> bool {
+        self.employee_groups.is_empty()
+            || employee_group.is_some_and(|group| self.employee_groups.iter().any(|g| g == group))
+    }
+}
+
+/// Public holidays of one year from Nager.Date.
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
 #[serde(rename_all = "camelCase")]
 pub struct HolidayCacheEntry {
     pub year: i32,
     pub fetched_at: String,
     pub holidays: Vec<CachedHoliday>,
+    /// False for entries cached when only one state's holidays were kept; they are refetched.
+    #[serde(default)]
+    pub all_subdivisions: bool,
+}
+
+/// Public holidays of one year from the built-in rules, kept until Nager.Date answers.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ComputedHolidayCacheEntry {
+    pub year: i32,
+    pub computed_at: String,
+    pub holidays: Vec<CachedHoliday>,
+}
+
+/// An imported ICS calendar with one holiday per covered day.
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct IcsHolidayCacheEntry {
+    /// Unique display name, e.g. "Schulferien MV".
+    pub name: String,
+    /// Refetched periodically if set; None for calendars imported from a file.
+    #[serde(default)]
+    pub url: Option<String>,
+    /// Subdivision the calendar applies to; None = every employee.
+    #[serde(default)]
+    pub subdivision: Option<String>,
+    pub fetched_at: String,
+    pub holidays: Vec<CachedHoliday>,
 }
 
 // Kept separate from holidays::Holiday so the on-disk cache schema and the API
@@ -124,6 +616,11 @@ pub struct HolidayCacheEntry {
 pub struct CachedHoliday {
     pub date: String,
     pub name: String,
+    #[serde(default)]
+    pub global: bool,
+    /// Subdivisions a non-global holiday applies to, e.g. ["DE-BY", "DE-BW"].
+    #[serde(default)]
+    pub subdivisions: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
@@ -161,6 +658,87 @@ pub enum StoreErrorCode {
 mod tests {
     use super::*;
 
+    #[test]
+    fn working_time_model_for_prefers_employee_override() {
+        let part_time = WorkingTimeModel {
+            working_days: vec![WorkingDay::Monday, WorkingDay::Tuesday],
+            start_time: "07:00".to_string(),
+            end_time: "12:00".to_string(),
+            weekly_hours: 10,
+        };
+        let store = LocalStore {
+            employee_settings: vec![
+                EmployeeSetting {
+                    daylite_contact_reference: "/v1/contacts/1".to_string(),
+                    working_time_model: Some(part_time.clone()),
+                    ..EmployeeSetting::default()
+                },
+                EmployeeSetting {
+                    daylite_contact_reference: "/v1/contacts/2".to_string(),
+                    ..EmployeeSetting::default()
+                },
+            ],
+            ..LocalStore::default()
+        };
+
+        assert_eq!(store.working_time_model_for("/v1/contacts/1"), &part_time);
+        assert_eq!(
+            store.working_time_model_for("/v1/contacts/2"),
+            &WorkingTimeModel::default()
+        );
+        assert_eq!(
+            store.working_time_model_for("/v1/contacts/404"),
+            &WorkingTimeModel::default()
+        );
+    }
+
+    #[test]
+    fn daily_hours_spread_weekly_hours_over_working_days() {
+        let part_time = WorkingTimeModel {
+            working_days: vec![WorkingDay::Monday, WorkingDay::Tuesday],
+            weekly_hours: 10,
+            ..WorkingTimeModel::default()
+        };
+        let no_days = WorkingTimeModel {
+            working_days: vec![],
+            ..WorkingTimeModel::default()
+        };
+
+        assert_eq!(WorkingTimeModel::default().daily_hours(), 8.0);
+        assert_eq!(part_time.daily_hours(), 5.0);
+        assert_eq!(no_days.daily_hours(), 0.0);
+    }
+
+    #[test]
+    fn works_on_checks_the_weekday() {
+        let model = WorkingTimeModel::default();
+
+        assert!(model.works_on(NaiveDate::from_ymd_opt(2026, 4, 27).unwrap()));
+        assert!(!model.works_on(NaiveDate::from_ymd_opt(2026, 5, 2).unwrap()));
+    }
+
+    #[test]
+    fn employee_for_primary_calendar_ignores_trailing_slash() {
+        let store = LocalStore {
+            employee_settings: vec![EmployeeSetting {
+                daylite_contact_reference: "/v1/contacts/1".to_string(),
+                zep_primary_calendar: Some("https://app.zep.de/caldav/emp-1".to_string()),
+                ..EmployeeSetting::default()
+            }],
+            ..LocalStore::default()
+        };
+
+        let found = store.employee_for_primary_calendar("https://app.zep.de/caldav/emp-1/");
+
+        assert_eq!(
+            found.map(|s| s.daylite_contact_reference.as_str()),
+            Some("/v1/contacts/1")
+        );
+        assert!(store
+            .employee_for_primary_calendar("https://app.zep.de/caldav/emp-2/")
+            .is_none());
+    }
+
     #[test]
     fn cleanup_removes_entries_older_than_one_year() {
         let today = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
@@ -170,11 +748,13 @@ mod tests {
                     year: 2024,
                     fetched_at: "2024-07-01".to_string(),
                     holidays: vec![],
+                    all_subdivisions: true,
                 },
                 HolidayCacheEntry {
                     year: 2023,
                     fetched_at: "2024-06-29".to_string(),
                     holidays: vec![],
+                    all_subdivisions: true,
                 },
             ],
             ..LocalStore::default()
@@ -194,6 +774,7 @@ mod tests {
                 year: 2025,
                 fetched_at: "2025-06-01".to_string(),
                 holidays: vec![],
+                all_subdivisions: true,
             }],
             ..LocalStore::default()
         };
@@ -203,6 +784,31 @@ mod tests {
         assert_eq!(store.holiday_cache.len(), 1);
     }
 
+    #[test]
+    fn cleanup_drops_stale_computed_entries_but_keeps_imported_calendars() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+        let mut store = LocalStore {
+            computed_holiday_cache: vec![ComputedHolidayCacheEntry {
+                year: 2024,
+                computed_at: "2024-01-15".to_string(),
+                holidays: vec![],
+            }],
+            ics_holiday_calendars: vec![IcsHolidayCacheEntry {
+                name: "Schulferien MV".to_string(),
+                url: None,
+                subdivision: Some("DE-MV".to_string()),
+                fetched_at: "2023-09-01".to_string(),
+                holidays: vec![],
+            }],
+            ..LocalStore::default()
+        };
+
+        store.cleanup_holiday_cache(today);
+
+        assert!(store.computed_holiday_cache.is_empty());
+        assert_eq!(store.ics_holiday_calendars.len(), 1);
+    }
+
     #[test]
     fn cleanup_removes_all_entries_when_all_expired() {
         let today = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
@@ -212,11 +818,13 @@ mod tests {
                     year: 2023,
                     fetched_at: "2024-06-29".to_string(),
                     holidays: vec![],
+                    all_subdivisions: true,
                 },
                 HolidayCacheEntry {
                     year: 2022,
                     fetched_at: "2023-01-01".to_string(),
                     holidays: vec![],
+                    all_subdivisions: true,
                 },
             ],
             ..LocalStore::default()
@@ -242,4 +850,112 @@ mod tests {
         assert!(!DisplaySettings::default().show_weekend);
         assert!(!LocalStore::default().display_settings.show_weekend);
     }
+
+    #[test]
+    fn blank_planning_contact_settings_fall_back_to_defaults() {
+        let settings = PlanningContactSettings {
+            categories: vec![" ".to_string()],
+            primary_ical_label: "  ".to_string(),
+            absence_ical_label: " Fehlzeiten iCal ".to_string(),
+        };
+
+        assert_eq!(settings.planning_categories(), vec!["Monteur", "Test"]);
+        assert_eq!(settings.primary_label(), "Einsatz iCal");
+        assert_eq!(settings.absence_label(), "Fehlzeiten iCal");
+    }
+
+    #[test]
+    fn partial_background_sync_settings_keep_the_other_defaults() {
+        let settings: BackgroundSyncSettings =
+            serde_json::from_str(r#"{"projectsIntervalMinutes":5}"#).unwrap();
+
+        assert!(!settings.enabled);
+        assert_eq!(settings.projects_interval_minutes, 5);
+        assert_eq!(
+            settings.contacts_interval_minutes,
+            BackgroundSyncSettings::default().contacts_interval_minutes
+        );
+    }
+
+    #[test]
+    fn holiday_subdivision_falls_back_to_the_company_default() {
+        let mut store = LocalStore {
+            employee_settings: vec![EmployeeSetting {
+                daylite_contact_reference: "/v1/contacts/1".to_string(),
+                holiday_subdivision: Some("DE-BY".to_string()),
+                ..EmployeeSetting::default()
+            }],
+            ..LocalStore::default()
+        };
+
+        assert_eq!(store.holiday_subdivision_for("/v1/contacts/1"), "DE-BY");
+        assert_eq!(
+            store.holiday_subdivision_for("/v1/contacts/2"),
+            FALLBACK_HOLIDAY_SUBDIVISION
+        );
+
+        store.default_holiday_subdivision = Some("DE-BW".to_string());
+        assert_eq!(store.holiday_subdivision_for("/v1/contacts/2"), "DE-BW");
+    }
+
+    #[test]
+    fn legacy_holiday_cache_entries_are_marked_for_refetch() {
+        let entry: HolidayCacheEntry = serde_json::from_str(
+            r#"{"year":2024,"fetchedAt":"2024-06-01","holidays":[{"date":"2024-01-01","name":"Neujahr"}]}"#,
+        )
+        .unwrap();
+
+        assert!(!entry.all_subdivisions);
+        assert!(!entry.holidays[0].global);
+    }
+
+    #[test]
+    fn closure_dates_are_clipped_to_the_requested_range() {
+        let closure = ClosureDay {
+            name: "Betriebsferien".to_string(),
+            start_date: "2026-12-24".to_string(),
+            end_date: Some("2027-01-01".to_string()),
+            employee_groups: vec![],
+        };
+        let start = NaiveDate::from_ymd_opt(2026, 12, 28).unwrap();
+        let end = NaiveDate::from_ymd_opt(2027, 1, 3).unwrap();
+
+        let dates = closure.dates_within(start, end);
+
+        assert_eq!(dates.len(), 5);
+        assert_eq!(dates[0], start);
+        assert_eq!(dates[4], NaiveDate::from_ymd_opt(2027, 1, 1).unwrap());
+    }
+
+    #[test]
+    fn inverted_or_invalid_closures_cover_nothing() {
+        let start = NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2026, 12, 31).unwrap();
+        let inverted = ClosureDay {
+            start_date: "2026-05-10".to_string(),
+            end_date: Some("2026-05-01".to_string()),
+            ..ClosureDay::default()
+        };
+        let invalid = ClosureDay {
+            start_date: "Mai".to_string(),
+            ..ClosureDay::default()
+        };
+
+        assert!(inverted.dates_within(start, end).is_empty());
+        assert!(invalid.dates_within(start, end).is_empty());
+    }
+
+    #[test]
+    fn closures_without_groups_apply_to_everyone() {
+        let everyone = ClosureDay::default();
+        let workshop = ClosureDay {
+            employee_groups: vec!["Werkstatt".to_string()],
+            ..ClosureDay::default()
+        };
+
+        assert!(everyone.applies_to_group(None));
+        assert!(workshop.applies_to_group(Some("Werkstatt")));
+        assert!(!workshop.applies_to_group(Some("Montage")));
+        assert!(!workshop.applies_to_group(None));
+    }
 }
@@ -5,4 +5,5 @@ pub mod holidays;
 #[cfg(test)]
 pub(crate) mod http_record_replay;
 pub mod local_store;
+pub mod scheduler;
 pub mod zep;
@@ -0,0 +1,328 @@
+use chrono::{DateTime, Datelike, Duration, SecondsFormat, Utc};
+use serde::{Deserialize, Serialize};
+use specta::Type;
+use std::collections::HashMap;
+use tauri::Emitter;
+use tauri_plugin_http::reqwest;
+
+use crate::integrations::daylite::contacts::{apply_contacts_to_store, fetch_planning_contacts};
+use crate::integrations::daylite::project_cache::sync_project_cache;
+use crate::integrations::holidays::{apply_holiday_caches, prefetch_holidays};
+use crate::integrations::local_store::{
+    load_local_store, save_store_internal, BackgroundSyncSettings, EmployeeSetting, LocalStore,
+};
+use crate::integrations::zep::retest_calendar_urls;
+
+pub const BACKGROUND_SYNC_EVENT: &str = "background-sync";
+pub const CONNECTIVITY_EVENT: &str = "connectivity-changed";
+
+const TICK_SECS: u64 = 60;
+// Gives the UI's own start-up loads a head start before the first refresh.
+const STARTUP_DELAY_SECS: u64 = 15;
+const CONNECTIVITY_TIMEOUT_SECS: u64 = 5;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, Type, PartialEq, Eq, Hash)]
+#[serde(rename_all = "camelCase")]
+pub enum BackgroundTask {
+    Contacts,
+    Projects,
+    Holidays,
+    CalendarProbe,
+}
+
+impl BackgroundTask {
+    const ALL: [Self; 4] = [
+        Self::Contacts,
+        Self::Projects,
+        Self::Holidays,
+        Self::CalendarProbe,
+    ];
+
+    fn interval_minutes(self, settings: &BackgroundSyncSettings) -> u32 {
+        match self {
+            Self::Contacts => settings.contacts_interval_minutes,
+            Self::Projects => settings.projects_interval_minutes,
+            Self::Holidays => settings.holidays_interval_minutes,
+            Self::CalendarProbe => settings.calendar_probe_interval_minutes,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct BackgroundSyncEvent {
+    pub task: BackgroundTask,
+    pub success: bool,
+    // German summary for a status line, or the user message of the error.
+    pub message: String,
+    pub finished_at: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Type, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ConnectivityEvent {
+    pub online: bool,
+}
+
+/// Starts the background refreshes; they run for the lifetime of the app. Settings are
+/// re-read on every tick, so interval changes apply without a restart.
+pub fn start(app: tauri::AppHandle) {
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_secs(STARTUP_DELAY_SECS)).await;
+        let mut last_runs = HashMap::new();
+        let mut online = true;
+        loop {
+            tick(&app, &mut last_runs, &mut online).await;
+            tokio::time::sleep(std::time::Duration::from_secs(TICK_SECS)).await;
+        }
+    });
+}
+
+async fn tick(
+    app: &tauri::AppHandle,
+    last_runs: &mut HashMap<BackgroundTask, DateTime<Utc>>,
+    online: &mut bool,
+) {
+    let store = match load_local_store(app.clone()) {
+        Ok(store) => store,
+        Err(e) => {
+            eprintln!("scheduler: store load failed: {}", e.technical_message);
+            return;
+        }
+    };
+    let due = due_tasks(&store.background_sync, last_runs, Utc::now());
+    if due.is_empty() {
+        return;
+    }
+
+    // While offline nothing is marked as run, so every due task starts as soon as the
+    // connection is back.
+    let reachable = is_reachable(&store.api_endpoints.daylite_base_url).await;
+    if reachable != *online {
+        *online = reachable;
+        if let Err(error) = app.emit(CONNECTIVITY_EVENT, ConnectivityEvent { online: reachable }) {
+            eprintln!("scheduler: connectivity event failed: {error}");
+        }
+    }
+    if !reachable {
+        return;
+    }
+
+    for task in due {
+        let result = run_task(app, task, &store).await;
+        last_runs.insert(task, Utc::now());
+        if let Err(message) = &result {
+            eprintln!("scheduler: {task:?} failed: {message}");
+        }
+        let event = BackgroundSyncEvent {
+            task,
+            success: result.is_ok(),
+            message: result.unwrap_or_else(|message| message),
+            finished_at: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+        };
+        if let Err(error) = app.emit(BACKGROUND_SYNC_EVENT, event) {
+            eprintln!("scheduler: sync event failed: {error}");
+        }
+    }
+}
+
+/// Tasks whose interval has passed since their last run; tasks that have not run since the
+/// app started are due immediately.
+fn due_tasks(
+    settings: &BackgroundSyncSettings,
+    last_runs: &HashMap<BackgroundTask, DateTime<Utc>>,
+    now: DateTime<Utc>,
+) -> Vec<BackgroundTask> {
+    if !settings.enabled {
+        return Vec::new();
+    }
+    BackgroundTask::ALL
+        .into_iter()
+        .filter(|task| {
+            let interval = task.interval_minutes(settings);
+            interval > 0
+                && last_runs.get(task).is_none_or(|last_run| {
+                    now - *last_run >= Duration::minutes(i64::from(interval))
+                })
+        })
+        .collect()
+}
+
+/// The network work runs against `snapshot`; its results are applied to a freshly loaded
+/// store, so edits saved by the UI in the meantime are kept.
+async fn run_task(
+    app: &tauri::AppHandle,
+    task: BackgroundTask,
+    snapshot: &LocalStore,
+) -> Result<String, String> {
+    match task {
+        BackgroundTask::Contacts => {
+            let contacts = fetch_planning_contacts(
+                &snapshot.api_endpoints.daylite_base_url,
+                &snapshot.planning_contacts,
+            )
+            .await
+            .map_err(|e| e.user_message)?;
+            update_store(app, |store| apply_contacts_to_store(store, &contacts))?;
+            Ok(format!("{} Kontakte aktualisiert", contacts.len()))
+        }
+        BackgroundTask::Projects => {
+            let mut synced = snapshot.clone();
+            let result = sync_project_cache(&mut synced)
+                .await
+                .map_err(|e| e.user_message)?;
+            update_store(app, |store| {
+                store.daylite_cache.projects = synced.daylite_cache.projects;
+                store.daylite_cache.projects_synced_at = synced.daylite_cache.projects_synced_at;
+                store.daylite_cache.last_synced_at = synced.daylite_cache.last_synced_at;
+            })?;
+            Ok(format!(
+                "{} Projekte aktualisiert, {} entfernt",
+                result.updated, result.removed
+            ))
+        }
+        BackgroundTask::Holidays => {
+            let next_year = Utc::now().year() + 1;
+            let mut refreshed = snapshot.clone();
+            if prefetch_holidays(&mut refreshed, next_year).await? {
+                update_store(app, |store| apply_holiday_caches(store, &refreshed))?;
+            }
+            Ok(format!("Feiertage für {next_year} geladen"))
+        }
+        BackgroundTask::CalendarProbe => {
+            let mut probed = snapshot.employee_settings.clone();
+            let Some((passed, failed)) = retest_calendar_urls(&mut probed).await else {
+                return Ok("ZEP ist nicht eingerichtet".to_string());
+            };
+            update_store(app, |store| {
+                merge_probe_results(&mut store.employee_settings, &probed)
+            })?;
+            Ok(format!(
+                "{passed} Kalender erreichbar, {failed} nicht erreichbar"
+            ))
+        }
+    }
+}
+
+fn update_store(
+    app: &tauri::AppHandle,
+    update: impl FnOnce(&mut LocalStore),
+) -> Result<(), String> {
+    let mut store = load_local_store(app.clone()).map_err(|e| {
+        eprintln!("scheduler: store load failed: {}", e.technical_message);
+        "Lokale Daten konnten nicht geladen werden".to_string()
+    })?;
+    update(&mut store);
+    save_store_internal(app, store).map_err(|e| {
+        eprintln!("scheduler: store save failed: {}", e.technical_message);
+        "Lokale Daten konnten nicht gespeichert werden".to_string()
+    })
+}
+
+/// Copies probe results for calendar URLs that are still assigned; a URL changed while
+/// the probe ran keeps its own (cleared) result.
+fn merge_probe_results(settings: &mut [EmployeeSetting], probed: &[EmployeeSetting]) {
+    for setting in settings {
+        let Some(result) = probed
+            .iter()
+            .find(|p| p.daylite_contact_reference == setting.daylite_contact_reference)
+        else {
+            continue;
+        };
+        if setting.zep_primary_calendar == result.zep_primary_calendar {
+            setting.primary_ical_last_tested_at = result.primary_ical_last_tested_at.clone();
+            setting.primary_ical_last_test_passed = result.primary_ical_last_test_passed;
+        }
+        if setting.zep_absence_calendar == result.zep_absence_calendar {
+            setting.absence_ical_last_tested_at = result.absence_ical_last_tested_at.clone();
+            setting.absence_ical_last_test_passed = result.absence_ical_last_test_passed;
+        }
+    }
+}
+
+/// Any HTTP answer from the Daylite host counts as online; only connection failures and
+/// timeouts do not.
+async fn is_reachable(url: &str) -> bool {
+    let Ok(client) = reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(CONNECTIVITY_TIMEOUT_SECS))
+        .build()
+    else {
+        return false;
+    };
+    client.head(url).send().await.is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn at(value: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(value)
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    #[test]
+    fn tasks_are_due_at_start_and_after_their_interval() {
+        let settings = BackgroundSyncSettings {
+            enabled: true,
+            ..BackgroundSyncSettings::default()
+        };
+        let now = at("2026-03-02T10:00:00Z");
+
+        assert_eq!(
+            due_tasks(&settings, &HashMap::new(), now),
+            BackgroundTask::ALL.to_vec()
+        );
+
+        let last_runs = HashMap::from([
+            (BackgroundTask::Contacts, at("2026-03-02T09:30:00Z")),
+            (BackgroundTask::Projects, at("2026-03-02T09:45:00Z")),
+            (BackgroundTask::Holidays, at("2026-03-02T09:00:00Z")),
+            (BackgroundTask::CalendarProbe, at("2026-03-02T09:00:00Z")),
+        ]);
+        assert_eq!(
+            due_tasks(&settings, &last_runs, now),
+            vec![BackgroundTask::Projects]
+        );
+    }
+
+    #[test]
+    fn zero_interval_or_disabled_sync_runs_nothing() {
+        let settings = BackgroundSyncSettings {
+            enabled: true,
+            contacts_interval_minutes: 0,
+            ..BackgroundSyncSettings::default()
+        };
+        let now = at("2026-03-02T10:00:00Z");
+
+        assert!(!due_tasks(&settings, &HashMap::new(), now).contains(&BackgroundTask::Contacts));
+
+        let disabled = BackgroundSyncSettings::default();
+        assert!(due_tasks(&disabled, &HashMap::new(), now).is_empty());
+    }
+
+    #[test]
+    fn probe_results_are_only_merged_for_unchanged_urls() {
+        let setting = |primary: &str, absence: &str| EmployeeSetting {
+            daylite_contact_reference: "/v1/contacts/1".to_string(),
+            zep_primary_calendar: Some(primary.to_string()),
+            zep_absence_calendar: Some(absence.to_string()),
+            ..EmployeeSetting::default()
+        };
+        let probed = vec![EmployeeSetting {
+            primary_ical_last_tested_at: Some("2026-03-02T10:00:00.000Z".to_string()),
+            primary_ical_last_test_passed: Some(true),
+            absence_ical_last_tested_at: Some("2026-03-02T10:00:00.000Z".to_string()),
+            absence_ical_last_test_passed: Some(false),
+            ..setting("https://zep/a/", "https://zep/b/")
+        }];
+        let mut current = vec![setting("https://zep/a/", "https://zep/neu/")];
+
+        merge_probe_results(&mut current, &probed);
+
+        assert_eq!(current[0].primary_ical_last_test_passed, Some(true));
+        assert_eq!(current[0].absence_ical_last_tested_at, None);
+        assert_eq!(current[0].absence_ical_last_test_passed, None);
+    }
+}
@@ -5,5 +5,5 @@ mod settings;
 pub mod types;
 
 pub(crate) use credentials::load_zep_credentials_from_keychain;
-pub(crate) use settings::test_untested_calendar_urls;
+pub(crate) use settings::{retest_calendar_urls, test_untested_calendar_urls};
 pub(crate) use types::ZepStoredCredentials;
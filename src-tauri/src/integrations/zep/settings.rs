@@ -10,16 +10,29 @@ pub(super) fn current_timestamp() -> String {
 /// Silently does nothing if ZEP credentials are not configured: this runs on
 /// startup paths that must not error.
 pub(crate) async fn test_untested_calendar_urls(settings: &mut [EmployeeSetting]) {
+    probe_calendar_urls(settings, |last_tested_at| last_tested_at.is_none()).await;
+}
+
+/// Re-tests every assigned calendar URL. Returns the number of (passed, failed) probes, or
+/// None if ZEP credentials are not configured.
+pub(crate) async fn retest_calendar_urls(settings: &mut [EmployeeSetting]) -> Option<(u32, u32)> {
+    probe_calendar_urls(settings, |_| true).await
+}
+
+async fn probe_calendar_urls(
+    settings: &mut [EmployeeSetting],
+    needs_test: impl Fn(&Option<String>) -> bool,
+) -> Option<(u32, u32)> {
     let to_test: Vec<(String, String, bool)> = settings
         .iter()
         .flat_map(|s| {
             let mut items = vec![];
-            if s.primary_ical_last_tested_at.is_none() {
+            if needs_test(&s.primary_ical_last_tested_at) {
                 if let Some(ref url) = s.zep_primary_calendar {
                     items.push((s.daylite_contact_reference.clone(), url.clone(), true));
                 }
             }
-            if s.absence_ical_last_tested_at.is_none() {
+            if needs_test(&s.absence_ical_last_tested_at) {
                 if let Some(ref url) = s.zep_absence_calendar {
                     items.push((s.daylite_contact_reference.clone(), url.clone(), false));
                 }
@@ -29,19 +42,22 @@ pub(crate) async fn test_untested_calendar_urls(settings: &mut [EmployeeSetting]
         .collect();
 
     if to_test.is_empty() {
-        return;
+        return Some((0, 0));
     }
 
-    let creds = match load_zep_credentials_from_keychain() {
-        Ok(c) => c,
-        Err(_) => return,
-    };
+    let creds = load_zep_credentials_from_keychain().ok()?;
 
     let timestamp = current_timestamp();
+    let (mut passed, mut failed) = (0, 0);
     for (reference, url, is_primary) in &to_test {
         let success = probe_calendar(url, &creds.username, &creds.password)
             .await
             .is_ok();
+        if success {
+            passed += 1;
+        } else {
+            failed += 1;
+        }
         if let Some(setting) = settings
             .iter_mut()
             .find(|s| s.daylite_contact_reference == *reference)
@@ -55,6 +71,7 @@ pub(crate) async fn test_untested_calendar_urls(settings: &mut [EmployeeSetting]
             }
         }
     }
+    Some((passed, failed))
 }
 
 pub(super) fn find_or_default_setting(
@@ -106,6 +123,9 @@ mod tests {
                 primary_ical_last_test_passed: Some(true),
                 absence_ical_last_tested_at: Some("2026-01-01T12:00:00.000Z".to_string()),
                 absence_ical_last_test_passed: Some(false),
+                working_time_model: None,
+                holiday_subdivision: None,
+                employee_group: None,
             }];
 
             test_untested_calendar_urls(&mut settings).await;
@@ -137,6 +157,9 @@ mod tests {
                 primary_ical_last_test_passed: None,
                 absence_ical_last_tested_at: None,
                 absence_ical_last_test_passed: None,
+                working_time_model: None,
+                holiday_subdivision: None,
+                employee_group: None,
             }];
 
             test_untested_calendar_urls(&mut settings).await;
@@ -157,6 +180,9 @@ mod tests {
                 primary_ical_last_test_passed: None,
                 absence_ical_last_tested_at: None,
                 absence_ical_last_test_passed: None,
+                working_time_model: None,
+                holiday_subdivision: None,
+                employee_group: None,
             }];
 
             test_untested_calendar_urls(&mut settings).await;
@@ -188,6 +214,9 @@ mod tests {
             primary_ical_last_test_passed: Some(true),
             absence_ical_last_tested_at: None,
             absence_ical_last_test_passed: None,
+            working_time_model: None,
+            holiday_subdivision: None,
+            employee_group: None,
         }];
         update_setting(&mut settings, "/v1/contacts/42", |s| {
             s.zep_primary_calendar = Some("https://cal.example/".to_string());
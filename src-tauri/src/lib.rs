@@ -10,16 +10,32 @@ fn specta_builder() -> tauri_specta::Builder<tauri::Wry> {
         integrations::local_store::save_local_store,
         integrations::calendar::commands::load_week_events,
         integrations::holidays::get_holidays_for_week,
+        integrations::holidays::get_holidays_for_range,
+        integrations::holidays::get_holidays_for_year,
+        integrations::holidays::get_holidays_by_subdivision_for_week,
+        integrations::holidays::import_holiday_ics,
+        integrations::holidays::remove_holiday_ics,
         integrations::daylite::auth::daylite_connect_refresh_token,
         integrations::daylite::projects::daylite_list_projects,
         integrations::daylite::projects::daylite_search_projects,
+        integrations::daylite::project_search::daylite_search_cached_projects,
+        integrations::daylite::project_details::daylite_get_project_details,
         integrations::daylite::projects::daylite_query_overdue_projects,
+        integrations::daylite::project_cache::daylite_sync_project_cache,
         integrations::daylite::contacts::commands::daylite_list_contacts,
         integrations::daylite::contacts::commands::daylite_list_cached_contacts,
         integrations::daylite::contacts::commands::daylite_update_contact_ical_urls,
         integrations::calendar::commands::create_assignment,
         integrations::calendar::commands::update_assignment,
         integrations::calendar::commands::delete_assignment,
+        integrations::calendar::commands::reorder_assignments,
+        integrations::calendar::staffing::get_project_staffing_report,
+        integrations::calendar::timeline::get_project_timeline,
+        integrations::calendar::orphans::scan_orphaned_assignments,
+        integrations::calendar::orphans::delete_orphaned_assignments,
+        integrations::calendar::orphans::relink_orphaned_assignments,
+        integrations::calendar::title_sync::reconcile_assignment_titles,
+        integrations::calendar::lint::lint_week_assignments,
         integrations::zep::commands::zep_save_credentials,
         integrations::zep::commands::zep_load_credentials,
         integrations::zep::commands::zep_test_credentials,
@@ -50,6 +66,8 @@ pub fn run() {
                 eprintln!("Failed to initialize credential store: {error}");
             }
 
+            integrations::scheduler::start(app.handle().clone());
+
             let handle = app.handle().clone();
             tauri::async_runtime::spawn(async move {
                 if let Some(message) = format_update_error(update(handle).await) {